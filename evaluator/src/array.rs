@@ -3,7 +3,7 @@ use std::rc::Rc;
 
 use parser::ast::Expression;
 
-use super::{env::Environment, eval_expression, object::Object, EvaluationError};
+use super::{env::Environment, eval_expression, object::Object, EvaluationError, Signal};
 
 pub fn eval_indexing(
     arr: Vec<Object>,
@@ -12,10 +12,10 @@ pub fn eval_indexing(
 
     match index {
         Object::Integer(i) => {
-            if arr.len() <= i as usize {
+            if i < 0 || arr.len() <= i as usize {
                 return Err(EvaluationError::IndexOutOfBounds {
                     value: Object::Array(arr),
-                    index: i as usize,
+                    index: i,
                 });
             }
             Ok(arr[i as usize].clone())
@@ -24,11 +24,31 @@ pub fn eval_indexing(
     }
 }
 
-pub fn eval_array(
-    env: &Rc<RefCell<Environment>>,
-    array: &[Expression],
+pub fn eval_index_assignment(
+    mut arr: Vec<Object>,
+    index: Object,
+    value: Object,
 ) -> Result<Object, EvaluationError> {
-    let values: Result<Vec<Object>, EvaluationError> =
+    match index {
+        Object::Integer(i) => {
+            if i < 0 || arr.len() <= i as usize {
+                return Err(EvaluationError::IndexOutOfBounds {
+                    value: Object::Array(arr),
+                    index: i,
+                });
+            }
+            arr[i as usize] = value;
+            Ok(Object::Array(arr))
+        }
+        i => Err(EvaluationError::NotIndexable {
+            value: Object::Array(arr),
+            index: Some(i),
+        }),
+    }
+}
+
+pub fn eval_array(env: &Rc<RefCell<Environment>>, array: &[Expression]) -> Result<Object, Signal> {
+    let values: Result<Vec<Object>, Signal> =
         array.iter().map(|ex| eval_expression(env, ex)).collect();
 
     match values {
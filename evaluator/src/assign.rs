@@ -0,0 +1,113 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use parser::ast::{Expression, InfixOperation};
+
+use super::{
+    array, env::Environment, eval_expression, eval_indexing, hashmap, infix, lookup_variable,
+    object::Object, undefined_variable, EvaluationError, Signal,
+};
+
+// Value to store for an assignment: the new value itself, or the result of
+// applying a compound assignment operation to the current value
+fn assigned_value(
+    operation: &Option<InfixOperation>,
+    current: impl FnOnce() -> Result<Object, EvaluationError>,
+    value: Object,
+) -> Result<Object, EvaluationError> {
+    match operation {
+        Some(operation) => infix::apply(operation, current()?, value),
+        None => Ok(value),
+    }
+}
+
+fn set_variable(
+    env: &Rc<RefCell<Environment>>,
+    identifier: &str,
+    value: Object,
+) -> Result<(), EvaluationError> {
+    if !Environment::update_rr(env, identifier, value) {
+        return Err(undefined_variable(env, identifier));
+    }
+    Ok(())
+}
+
+pub fn eval_assign(
+    env: &Rc<RefCell<Environment>>,
+    identifier: &str,
+    operation: &Option<InfixOperation>,
+    expression: &Expression,
+) -> Result<(), Signal> {
+    let value = eval_expression(env, expression)?;
+    let value = assigned_value(operation, || lookup_variable(env, identifier), value)?;
+    set_variable(env, identifier, value).map_err(Signal::from)
+}
+
+fn eval_index_assignment(
+    collection: Object,
+    index: Object,
+    value: Object,
+) -> Result<Object, EvaluationError> {
+    match collection {
+        Object::Array(arr) => array::eval_index_assignment(arr, index, value),
+        Object::HashMap(hm) => hashmap::eval_index_assignment(hm, index, value),
+        v => Err(EvaluationError::NotIndexable {
+            value: v,
+            index: None,
+        }),
+    }
+}
+
+// Returns a copy of `collection` where the element at the path of `indices`
+// is replaced by the assigned value
+fn replace_element(
+    collection: Object,
+    indices: &[Object],
+    operation: &Option<InfixOperation>,
+    value: Object,
+) -> Result<Object, EvaluationError> {
+    let (index, rest) = indices.split_first().expect("There is at least one index");
+    let current = || eval_indexing(collection.clone(), index.clone());
+
+    let element = if rest.is_empty() {
+        assigned_value(operation, current, value)?
+    } else {
+        replace_element(current()?, rest, operation, value)?
+    };
+
+    eval_index_assignment(collection, index.clone(), element)
+}
+
+/// Evaluates `collection[index] = expression`. Collections are values, so the
+/// variable at the root of `collection` is updated with a modified copy.
+pub fn eval_index_assign(
+    env: &Rc<RefCell<Environment>>,
+    collection: &Expression,
+    index: &Expression,
+    operation: &Option<InfixOperation>,
+    expression: &Expression,
+) -> Result<(), Signal> {
+    // In `a[i][j]`, find `a` and the indices, outermost first
+    let mut index_expressions = vec![index];
+    let mut root = collection;
+    while let Expression::Index { array, index, .. } = root {
+        index_expressions.push(index);
+        root = array;
+    }
+
+    let indices = index_expressions
+        .iter()
+        .rev()
+        .map(|index| eval_expression(env, index))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let identifier = match root {
+        Expression::IdentifierExpression { identifier, .. } => identifier,
+        _ => unreachable!("The parser only accepts indexings of variables"),
+    };
+
+    let value = eval_expression(env, expression)?;
+    let root_v = lookup_variable(env, identifier)?;
+    let root_v = replace_element(root_v, &indices, operation, value)?;
+    set_variable(env, identifier, root_v).map_err(Signal::from)
+}
@@ -0,0 +1,98 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use parser::ast::Binding;
+
+use super::{env::Environment, object::Object, EvaluationError};
+
+/// Sets the names of a `let` binding to the matching parts of `value`. When
+/// the value does not have the shape of the binding no name is set.
+pub fn bind(
+    env: &Rc<RefCell<Environment>>,
+    binding: &Binding,
+    value: Object,
+) -> Result<(), EvaluationError> {
+    let mut bound = Vec::new();
+    destructure(binding, value, &mut bound)?;
+
+    for (name, value) in bound {
+        Environment::set_rr(env, name, value);
+    }
+    Ok(())
+}
+
+fn destructure(
+    binding: &Binding,
+    value: Object,
+    bound: &mut Vec<(String, Object)>,
+) -> Result<(), EvaluationError> {
+    match binding {
+        Binding::Identifier { name } => {
+            bound.push((name.clone(), value));
+            Ok(())
+        }
+        Binding::Array { elements, rest } => destructure_array(elements, rest, value, bound),
+        Binding::HashMap { keys } => destructure_hashmap(keys, value, bound),
+    }
+}
+
+fn destructure_array(
+    elements: &[Binding],
+    rest: &Option<String>,
+    value: Object,
+    bound: &mut Vec<(String, Object)>,
+) -> Result<(), EvaluationError> {
+    let mut arr = match value {
+        Object::Array(arr) => arr,
+        v => {
+            return Err(EvaluationError::UnexpectedType {
+                value: v,
+                expected: "array",
+            })
+        }
+    };
+
+    if arr.len() < elements.len() || (rest.is_none() && arr.len() > elements.len()) {
+        return Err(EvaluationError::ArrayLengthMismatch {
+            expected: elements.len(),
+            found: arr.len(),
+            rest: rest.is_some(),
+        });
+    }
+
+    let remaining = arr.split_off(elements.len());
+    for (element, value) in elements.iter().zip(arr) {
+        destructure(element, value, bound)?;
+    }
+    if let Some(rest) = rest {
+        bound.push((rest.clone(), Object::Array(remaining)));
+    }
+    Ok(())
+}
+
+// Objects that can be used as keys have no interior mutability, see
+// `hashmap::check_key_type`
+#[allow(clippy::mutable_key_type)]
+fn destructure_hashmap(
+    keys: &[String],
+    value: Object,
+    bound: &mut Vec<(String, Object)>,
+) -> Result<(), EvaluationError> {
+    let hm = match value {
+        Object::HashMap(hm) => hm,
+        v => {
+            return Err(EvaluationError::UnexpectedType {
+                value: v,
+                expected: "hashmap",
+            })
+        }
+    };
+
+    for key in keys {
+        match hm.get(&Object::Str(key.clone())) {
+            Some(value) => bound.push((key.clone(), value.clone())),
+            None => return Err(EvaluationError::MissingKey { key: key.clone() }),
+        }
+    }
+    Ok(())
+}
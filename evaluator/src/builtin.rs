@@ -1,16 +1,45 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use super::{env::Environment, object::Object, EvaluationError};
+use super::{env::Environment, object::Object, range, Arity, EvaluationError};
+
+// The builtin functions and the number of arguments they take, which is checked
+// before calling them
+const BUILTINS: &[(&str, Arity)] = &[
+    ("len", Arity::exactly(1)),
+    ("first", Arity::exactly(1)),
+    ("last", Arity::exactly(1)),
+    ("rest", Arity::exactly(1)),
+    ("push", Arity::exactly(2)),
+    ("int", Arity::exactly(1)),
+    ("float", Arity::exactly(1)),
+    (
+        "range",
+        Arity {
+            min: 1,
+            max: Some(2),
+        },
+    ),
+    ("array", Arity::exactly(1)),
+    ("puts", Arity { min: 0, max: None }),
+];
+
+pub fn builtin_arity(name: &str) -> Arity {
+    match BUILTINS.iter().find(|(builtin, _)| *builtin == name) {
+        Some((_, arity)) => *arity,
+        None => panic!("Unknown builtin function {}", name),
+    }
+}
 
 pub fn eval_builtin_call(arg_values: Vec<Object>, name: String) -> Result<Object, EvaluationError> {
     match name.as_ref() {
         "len" => match &arg_values[..] {
             [Object::Str(v)] => Ok(Object::Integer(v.len() as i64)),
             [Object::Array(a)] => Ok(Object::Integer(a.len() as i64)),
+            [Object::Range(start, end)] => Ok(Object::Integer(range::len(*start, *end))),
             _ => Err(EvaluationError::InvalidArguments {
                 values: arg_values,
-                expected: "string or array",
+                expected: "string, array or range",
             }),
         },
         "first" => match &arg_values[..] {
@@ -71,6 +100,57 @@ pub fn eval_builtin_call(arg_values: Vec<Object>, name: String) -> Result<Object
             }),
         },
 
+        "int" => match &arg_values[..] {
+            [Object::Integer(i)] => Ok(Object::Integer(*i)),
+            // Truncates towards zero, saturating on overflow
+            [Object::Float(f)] => Ok(Object::Integer(*f as i64)),
+            [Object::Str(s)] => match s.trim().parse::<i64>() {
+                Ok(i) => Ok(Object::Integer(i)),
+                Err(_) => Err(EvaluationError::InvalidArguments {
+                    values: arg_values,
+                    expected: "a string containing an integer",
+                }),
+            },
+            _ => Err(EvaluationError::InvalidArguments {
+                values: arg_values,
+                expected: "integer, float or string",
+            }),
+        },
+
+        "float" => match &arg_values[..] {
+            [Object::Integer(i)] => Ok(Object::Float(*i as f64)),
+            [Object::Float(f)] => Ok(Object::Float(*f)),
+            [Object::Str(s)] => match s.trim().parse::<f64>() {
+                Ok(f) => Ok(Object::Float(f)),
+                Err(_) => Err(EvaluationError::InvalidArguments {
+                    values: arg_values,
+                    expected: "a string containing a number",
+                }),
+            },
+            _ => Err(EvaluationError::InvalidArguments {
+                values: arg_values,
+                expected: "integer, float or string",
+            }),
+        },
+
+        "range" => match &arg_values[..] {
+            [Object::Integer(end)] => Ok(Object::Range(0, *end)),
+            [Object::Integer(start), Object::Integer(end)] => Ok(Object::Range(*start, *end)),
+            _ => Err(EvaluationError::InvalidArguments {
+                values: arg_values,
+                expected: "(integer) or (integer, integer)",
+            }),
+        },
+
+        "array" => match &arg_values[..] {
+            [Object::Array(a)] => Ok(Object::Array(a.clone())),
+            [Object::Range(start, end)] => Ok(range::to_array(*start, *end)),
+            _ => Err(EvaluationError::InvalidArguments {
+                values: arg_values,
+                expected: "array or range",
+            }),
+        },
+
         "puts" => {
             for (index, item) in arg_values.iter().enumerate() {
                 print!("{}", item);
@@ -88,39 +168,11 @@ pub fn eval_builtin_call(arg_values: Vec<Object>, name: String) -> Result<Object
 }
 
 pub fn set_builtins_to_env(env: &Rc<RefCell<Environment>>) {
-    Environment::set_rr(
-        env,
-        String::from("len"),
-        Object::BuiltInFunction(String::from("len")),
-    );
-
-    Environment::set_rr(
-        env,
-        String::from("first"),
-        Object::BuiltInFunction(String::from("first")),
-    );
-
-    Environment::set_rr(
-        env,
-        String::from("last"),
-        Object::BuiltInFunction(String::from("last")),
-    );
-
-    Environment::set_rr(
-        env,
-        String::from("rest"),
-        Object::BuiltInFunction(String::from("rest")),
-    );
-
-    Environment::set_rr(
-        env,
-        String::from("push"),
-        Object::BuiltInFunction(String::from("push")),
-    );
-
-    Environment::set_rr(
-        env,
-        String::from("puts"),
-        Object::BuiltInFunction(String::from("puts")),
-    );
+    for (name, _) in BUILTINS {
+        Environment::set_rr(
+            env,
+            String::from(*name),
+            Object::BuiltInFunction(String::from(*name)),
+        );
+    }
 }
@@ -3,23 +3,26 @@ use std::rc::Rc;
 
 use parser::ast::{Expression, Statement};
 
-use super::{eval_expression, eval_statements, object::Object, EvaluationError, env::Environment};
+use super::{
+    env::Environment, eval_block, eval_expression, object::Object, EvaluationError, Signal,
+};
 
 pub fn eval(
     env: &Rc<RefCell<Environment>>,
     condition: &Expression,
     consequence: &[Statement],
     alternative: &Option<Vec<Statement>>,
-) -> Result<Object, EvaluationError> {
+) -> Result<Object, Signal> {
     match eval_expression(env, condition)? {
-        Object::Bool(true) => eval_statements(env, consequence),
+        Object::Bool(true) => eval_block(env, consequence),
         Object::Bool(false) => match alternative {
             None => Ok(Object::Null),
-            Some(alt) => eval_statements(env, alt),
+            Some(alt) => eval_block(env, alt),
         },
         v => Err(EvaluationError::UnexpectedType {
             value: v,
             expected: "bool",
-        }),
+        }
+        .into()),
     }
 }
@@ -0,0 +1,165 @@
+use parser::diagnostics::Diagnostic;
+use parser::Span;
+
+use super::{object::Object, Arity, EvaluationError};
+
+fn type_names(values: &[Object]) -> String {
+    values
+        .iter()
+        .map(|v| v.type_name())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn arguments(count: usize) -> String {
+    match count {
+        1 => String::from("1 argument"),
+        n => format!("{} arguments", n),
+    }
+}
+
+impl std::fmt::Display for Arity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.max {
+            Some(max) if max == self.min => write!(f, "{}", arguments(max)),
+            Some(max) => write!(f, "{} to {}", self.min, arguments(max)),
+            None => write!(f, "at least {}", arguments(self.min)),
+        }
+    }
+}
+
+impl EvaluationError {
+    /// Errors that were not produced while evaluating an expression have no
+    /// location, so they point to the first character of the source.
+    pub fn diagnostic(&self) -> Diagnostic {
+        let span = self.span().unwrap_or_else(|| Span::new(0, 0, 1, 1));
+        let diagnostic = Diagnostic::new(self.to_string(), span);
+
+        let error = match self {
+            EvaluationError::Located { error, .. } => error,
+            e => e,
+        };
+        match error {
+            EvaluationError::UndefinedVariable {
+                suggestion: Some(suggestion),
+                ..
+            } => diagnostic.with_note(format!("did you mean `{}`?", suggestion), None),
+            _ => diagnostic,
+        }
+    }
+}
+
+impl std::fmt::Display for EvaluationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EvaluationError::InfixOperationNotImplemented {
+                operation,
+                left,
+                right,
+            } => write!(
+                f,
+                "cannot apply `{}` to {} and {}",
+                operation,
+                left.type_name(),
+                right.type_name()
+            ),
+            EvaluationError::PrefixOperationNotImplemented { operation, right } => {
+                write!(f, "cannot apply `{}` to {}", operation, right.type_name())
+            }
+            EvaluationError::InvalidArguments { values, expected } => write!(
+                f,
+                "invalid arguments ({}), expected {}",
+                type_names(values),
+                expected
+            ),
+            EvaluationError::UnexpectedType { value, expected } => {
+                write!(f, "expected {}, found {}", expected, value.type_name())
+            }
+            EvaluationError::IndexOutOfBounds { value, index } => write!(
+                f,
+                "index {} is out of bounds for {} `{}`",
+                index,
+                value.type_name(),
+                value
+            ),
+            EvaluationError::NotHashable { value } => {
+                write!(f, "{} can not be used as a hashmap key", value.type_name())
+            }
+            EvaluationError::NotCallable { value } => {
+                write!(f, "{} is not callable", value.type_name())
+            }
+            EvaluationError::NotIndexable {
+                value,
+                index: Some(index),
+            } => write!(
+                f,
+                "{} can not be indexed by {}",
+                value.type_name(),
+                index.type_name()
+            ),
+            EvaluationError::NotIndexable { value, index: None } => {
+                write!(f, "{} can not be indexed", value.type_name())
+            }
+            EvaluationError::DivisionByZero { operation } => {
+                write!(f, "cannot apply `{}` with a divisor of zero", operation)
+            }
+            EvaluationError::InvalidShift { count } => {
+                write!(f, "cannot shift by {} bits, expected 0 to 63", count)
+            }
+            EvaluationError::IntegerOverflow { operation } => {
+                write!(f, "integer overflow in `{}`", operation)
+            }
+            EvaluationError::PrefixIntegerOverflow { operation } => {
+                write!(f, "integer overflow in `{}`", operation)
+            }
+            EvaluationError::UndefinedVariable { name, .. } => {
+                write!(f, "cannot find variable `{}`", name)
+            }
+            EvaluationError::ArrayLengthMismatch {
+                expected,
+                found,
+                rest,
+            } => write!(
+                f,
+                "expected an array of {}{} elements, found {}",
+                if *rest { "at least " } else { "" },
+                expected,
+                found
+            ),
+            EvaluationError::MissingKey { key } => {
+                write!(f, "hashmap has no key \"{}\"", key)
+            }
+            EvaluationError::NoMatchingArm { value } => write!(
+                f,
+                "no arm of `match` matches {} `{}`",
+                value.type_name(),
+                value
+            ),
+            EvaluationError::UnknownKeywordArgument { name } => {
+                write!(f, "unknown keyword argument `{}`", name)
+            }
+            EvaluationError::DuplicateArgument { name } => {
+                write!(f, "argument `{}` is given more than once", name)
+            }
+            EvaluationError::WrongArgumentCount {
+                expected,
+                got,
+                function,
+            } => {
+                let function = match function {
+                    Some(name) => format!("function `{}`", name),
+                    None => String::from("function"),
+                };
+                let verb = if *got == 1 { "was" } else { "were" };
+                write!(
+                    f,
+                    "{} takes {}, but {} {} given",
+                    function, expected, got, verb
+                )
+            }
+            EvaluationError::Located { error, .. } => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for EvaluationError {}
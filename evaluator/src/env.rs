@@ -35,22 +35,101 @@ impl Environment {
         mutref.set(name, value);
     }
 
-    pub fn get_rr(rr: &Rc<RefCell<Self>>, name: &str) -> Object {
+    pub fn get_rr(rr: &Rc<RefCell<Self>>, name: &str) -> Option<Object> {
         let mutref = rr.borrow();
         mutref.get(name)
     }
 
+    pub fn update_rr(rr: &Rc<RefCell<Self>>, name: &str, value: Object) -> bool {
+        let mut mutref = rr.borrow_mut();
+        mutref.update(name, value)
+    }
+
     pub fn set(&mut self, name: String, value: Object) {
         self.store.insert(name, value);
     }
 
-    pub fn get(&self, name: &str) -> Object {
+    /// Returns the value of a variable, looking it up in the parent
+    /// environments if it is not defined in this one.
+    pub fn get(&self, name: &str) -> Option<Object> {
         match self.store.get(name) {
-            Some(v) => v.clone(),
+            Some(v) => Some(v.clone()),
             None => match &self.parent {
-                Some(parent) => Self::get_rr(&parent, name),
-                None => Object::Null,
+                Some(parent) => Self::get_rr(parent, name),
+                None => None,
             },
         }
     }
+
+    /// Returns the names of all the variables visible from this environment,
+    /// including the ones shadowed by an inner environment.
+    fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.store.keys().cloned().collect();
+        if let Some(parent) = &self.parent {
+            names.extend(parent.borrow().names());
+        }
+        names
+    }
+
+    /// Returns the visible variable whose name is closest to `name`, if any is
+    /// close enough to be a likely typo of it.
+    pub fn similar_name(&self, name: &str) -> Option<String> {
+        // Replacing every character is not a typo, so `y` never suggests `x`
+        let length = name.chars().count();
+        let max_distance = (length.max(3) / 3).min(length.saturating_sub(1));
+
+        self.names()
+            .into_iter()
+            .map(|candidate| (edit_distance(name, &candidate), candidate))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .min()
+            .map(|(_, candidate)| candidate)
+    }
+
+    /// Changes the value of an existing variable, in the closest environment
+    /// that defines it. Returns false if the variable is not defined.
+    pub fn update(&mut self, name: &str, value: Object) -> bool {
+        match self.store.get_mut(name) {
+            Some(v) => {
+                *v = value;
+                true
+            }
+            None => match &self.parent {
+                Some(parent) => Self::update_rr(parent, name, value),
+                None => false,
+            },
+        }
+    }
+}
+
+// Number of insertions, deletions, substitutions and transpositions of adjacent
+// characters needed to turn `a` into `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    // `rows[i][j]` is the distance between the first `i` characters of `a`
+    // and the first `j` characters of `b`
+    let mut rows = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, distance) in rows[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = distance;
+        }
+    }
+
+    rows[a.len()][b.len()]
 }
@@ -1,59 +1,172 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 
-use parser::ast::{Expression, Statement};
+use parser::ast::{Expression, Parameter, Statement};
 
 use super::{
-    builtin::eval_builtin_call, env::Environment, eval_expression, eval_statements, object::Object,
-    EvaluationError,
+    builtin::{builtin_arity, eval_builtin_call},
+    env::Environment,
+    eval_block, eval_expression,
+    object::{Function, Object},
+    Arity, EvaluationError, Signal,
 };
 
 pub fn eval_call(
     env: &Rc<RefCell<Environment>>,
     function: &Expression,
     arguments: &[Expression],
-) -> Result<Object, EvaluationError> {
+    keyword_arguments: &[(String, Expression)],
+) -> Result<Object, Signal> {
     let function_value = eval_expression(env, function)?;
 
     let arg_values = arguments
         .iter()
-        .flat_map(|a| eval_expression(env, a))
-        .collect::<Vec<_>>();
+        .map(|a| eval_expression(env, a))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let keyword_values = eval_keyword_arguments(env, keyword_arguments)?;
+
+    let name = match function {
+        Expression::IdentifierExpression { identifier, .. } => Some(identifier.as_str()),
+        _ => None,
+    };
 
     match function_value {
-        Object::Function(arg_names, body, clojure) => {
-            eval_monkey_call(arg_values, arg_names, body, clojure)
+        Object::Function(function) => eval_monkey_call(arg_values, keyword_values, &function, name),
+        Object::BuiltInFunction(name) => {
+            eval_builtin(arg_values, keyword_values, name).map_err(Signal::from)
         }
-        Object::BuiltInFunction(name) => eval_builtin_call(arg_values, name),
         _ => Err(EvaluationError::NotCallable {
             value: function_value.clone(),
-        }),
+        }
+        .into()),
     }
 }
 
-fn eval_monkey_call(
+fn eval_keyword_arguments(
+    env: &Rc<RefCell<Environment>>,
+    keyword_arguments: &[(String, Expression)],
+) -> Result<Vec<(String, Object)>, Signal> {
+    keyword_arguments
+        .iter()
+        .map(|(name, a)| Ok((name.clone(), eval_expression(env, a)?)))
+        .collect()
+}
+
+// Builtins only take positional arguments
+fn eval_builtin(
     arg_values: Vec<Object>,
-    arg_names: Vec<String>,
-    body: Vec<Statement>,
-    clojure: Rc<RefCell<Environment>>,
+    keyword_values: Vec<(String, Object)>,
+    name: String,
 ) -> Result<Object, EvaluationError> {
-    let new_env = Rc::new(RefCell::new(Environment::with_parent(&clojure)));
+    if let Some((keyword, _)) = keyword_values.into_iter().next() {
+        return Err(EvaluationError::UnknownKeywordArgument { name: keyword });
+    }
+
+    let arity = builtin_arity(&name);
+    if !arity.accepts(arg_values.len()) {
+        return Err(EvaluationError::WrongArgumentCount {
+            expected: arity,
+            got: arg_values.len(),
+            function: Some(name),
+        });
+    }
+    eval_builtin_call(arg_values, name)
+}
+
+fn eval_monkey_call(
+    arg_values: Vec<Object>,
+    keyword_values: Vec<(String, Object)>,
+    function: &Function,
+    name: Option<&str>,
+) -> Result<Object, Signal> {
+    let new_env = Rc::new(RefCell::new(Environment::with_parent(&function.env)));
+    let result = bind_arguments(&new_env, function, name, arg_values, keyword_values)
+        .and_then(|_| eval_block(&new_env, &function.body));
 
-    for (k, v) in arg_names.iter().zip(arg_values) {
-        Environment::set_rr(&new_env, k.to_owned(), v);
+    // A `return` in the body or in a default value ends the call
+    match result {
+        Err(Signal::Return(v)) => Ok(v),
+        result => result,
     }
+}
+
+// Sets the parameters of the function in the environment of the call. This is
+// kept out of `eval_monkey_call` so its stack frame is not alive during the
+// evaluation of the body, which matters for deeply recursive functions.
+fn bind_arguments(
+    env: &Rc<RefCell<Environment>>,
+    function: &Function,
+    name: Option<&str>,
+    mut arg_values: Vec<Object>,
+    keyword_values: Vec<(String, Object)>,
+) -> Result<(), Signal> {
+    let parameters = &function.parameters;
+    let got = arg_values.len() + keyword_values.len();
+    let wrong_count = || EvaluationError::WrongArgumentCount {
+        expected: arity(function),
+        got,
+        function: name.map(String::from),
+    };
+
+    if function.rest.is_none() && arg_values.len() > parameters.len() {
+        return Err(wrong_count().into());
+    }
+
+    let extra_values = arg_values.split_off(parameters.len().min(arg_values.len()));
+    let mut values = arg_values.into_iter().map(Some).collect::<Vec<_>>();
+    values.resize(parameters.len(), None);
+
+    for (name, value) in keyword_values {
+        match parameters.iter().position(|p| p.name == name) {
+            None => return Err(EvaluationError::UnknownKeywordArgument { name }.into()),
+            Some(i) if values[i].is_some() => {
+                return Err(EvaluationError::DuplicateArgument { name }.into())
+            }
+            Some(i) => values[i] = Some(value),
+        }
+    }
+
+    // Defaults are evaluated in order, so they can use the previous parameters
+    for (parameter, value) in parameters.iter().zip(values) {
+        let value = match (value, &parameter.default) {
+            (Some(value), _) => value,
+            (None, Some(default)) => eval_expression(env, default)?,
+            (None, None) => return Err(wrong_count().into()),
+        };
+        Environment::set_rr(env, parameter.name.clone(), value);
+    }
+
+    if let Some(rest) = &function.rest {
+        Environment::set_rr(env, rest.clone(), Object::Array(extra_values));
+    }
+    Ok(())
+}
 
-    eval_statements(&new_env, &body)
+fn arity(function: &Function) -> Arity {
+    Arity {
+        min: function
+            .parameters
+            .iter()
+            .filter(|p| p.default.is_none())
+            .count(),
+        max: match function.rest {
+            Some(_) => None,
+            None => Some(function.parameters.len()),
+        },
+    }
 }
 
 pub fn eval_function(
     env: &Rc<RefCell<Environment>>,
-    arguments: &[String],
+    arguments: &[Parameter],
+    rest: &Option<String>,
     body: &[Statement],
-) -> Result<Object, EvaluationError> {
-    Ok(Object::Function(
-        arguments.to_owned(),
-        body.to_owned(),
-        Rc::clone(env),
-    ))
+) -> Result<Object, Signal> {
+    Ok(Object::Function(Rc::new(Function {
+        parameters: arguments.to_owned(),
+        rest: rest.clone(),
+        body: body.to_owned(),
+        env: Rc::clone(env),
+    })))
 }
@@ -3,7 +3,7 @@ use std::{cell::RefCell, collections::HashMap};
 
 use parser::ast::Expression;
 
-use super::{env::Environment, eval_expression, object::Object, EvaluationError};
+use super::{env::Environment, eval_expression, object::Object, EvaluationError, Signal};
 
 pub fn check_key_type(key: &Object) -> Result<(), EvaluationError> {
     match key {
@@ -15,6 +15,8 @@ pub fn check_key_type(key: &Object) -> Result<(), EvaluationError> {
     }
 }
 
+// Objects that can be used as keys (see `check_key_type`) have no interior mutability
+#[allow(clippy::mutable_key_type)]
 pub fn eval_indexing(
     hm: HashMap<Object, Object>,
     index: Object,
@@ -26,10 +28,22 @@ pub fn eval_indexing(
     }
 }
 
+#[allow(clippy::mutable_key_type)]
+pub fn eval_index_assignment(
+    mut hm: HashMap<Object, Object>,
+    index: Object,
+    value: Object,
+) -> Result<Object, EvaluationError> {
+    check_key_type(&index)?;
+    hm.insert(index, value);
+    Ok(Object::HashMap(hm))
+}
+
+#[allow(clippy::mutable_key_type)]
 pub fn eval_hashmap(
     env: &Rc<RefCell<Environment>>,
     pairs: &[(Expression, Expression)],
-) -> Result<Object, EvaluationError> {
+) -> Result<Object, Signal> {
     let mut hm = HashMap::new();
 
     for (key_ex, val_ex) in pairs {
@@ -3,10 +3,20 @@ use std::rc::Rc;
 
 use parser::ast::{Expression, InfixOperation};
 
-use super::{env::Environment, eval_expression, object::Object, EvaluationError};
+use super::{env::Environment, eval_expression, object::Object, EvaluationError, Signal};
 
 type InfixFn = fn(&Object, &Object) -> Option<Object>;
 
+// When a float is operated with an integer, the integer is converted to float
+fn as_floats(a: &Object, b: &Object) -> Option<(f64, f64)> {
+    match (a, b) {
+        (Object::Float(a), Object::Float(b)) => Some((*a, *b)),
+        (Object::Integer(a), Object::Float(b)) => Some((*a as f64, *b)),
+        (Object::Float(a), Object::Integer(b)) => Some((*a, *b as f64)),
+        _ => None,
+    }
+}
+
 fn sum(a: &Object, b: &Object) -> Option<Object> {
     match (a, b) {
         (Object::Integer(a), Object::Integer(b)) => Some(Object::Integer(a + b)),
@@ -16,27 +26,113 @@ fn sum(a: &Object, b: &Object) -> Option<Object> {
             v.extend_from_slice(b);
             Some(Object::Array(v))
         },
-        _ => None,
+        _ => as_floats(a, b).map(|(a, b)| Object::Float(a + b)),
     }
 }
 
 fn subtraction(a: &Object, b: &Object) -> Option<Object> {
     match (a, b) {
         (Object::Integer(a), Object::Integer(b)) => Some(Object::Integer(a - b)),
-        _ => None,
+        _ => as_floats(a, b).map(|(a, b)| Object::Float(a - b)),
     }
 }
 
 fn division(a: &Object, b: &Object) -> Option<Object> {
     match (a, b) {
         (Object::Integer(a), Object::Integer(b)) => Some(Object::Integer(a / b)),
-        _ => None,
+        _ => as_floats(a, b).map(|(a, b)| Object::Float(a / b)),
     }
 }
 
 fn product(a: &Object, b: &Object) -> Option<Object> {
     match (a, b) {
         (Object::Integer(a), Object::Integer(b)) => Some(Object::Integer(a * b)),
+        _ => as_floats(a, b).map(|(a, b)| Object::Float(a * b)),
+    }
+}
+
+fn modulo(a: &Object, b: &Object) -> Option<Object> {
+    match (a, b) {
+        (Object::Integer(a), Object::Integer(b)) => Some(Object::Integer(a % b)),
+        _ => as_floats(a, b).map(|(a, b)| Object::Float(a % b)),
+    }
+}
+
+// A negative exponent gives a float: `2 ** -1` is `0.5`
+fn power(a: &Object, b: &Object) -> Option<Object> {
+    match (a, b) {
+        (Object::Integer(a), Object::Integer(b)) if *b >= 0 => {
+            Some(Object::Integer(a.pow(*b as u32)))
+        }
+        (Object::Integer(a), Object::Integer(b)) => {
+            Some(Object::Float((*a as f64).powf(*b as f64)))
+        }
+        _ => as_floats(a, b).map(|(a, b)| Object::Float(a.powf(b))),
+    }
+}
+
+fn and(a: &Object, b: &Object) -> Option<Object> {
+    match (a, b) {
+        (Object::Bool(a), Object::Bool(b)) => Some(Object::Bool(*a && *b)),
+        _ => None,
+    }
+}
+
+fn or(a: &Object, b: &Object) -> Option<Object> {
+    match (a, b) {
+        (Object::Bool(a), Object::Bool(b)) => Some(Object::Bool(*a || *b)),
+        _ => None,
+    }
+}
+
+fn bit_and(a: &Object, b: &Object) -> Option<Object> {
+    match (a, b) {
+        (Object::Integer(a), Object::Integer(b)) => Some(Object::Integer(a & b)),
+        (Object::Bool(a), Object::Bool(b)) => Some(Object::Bool(a & b)),
+        _ => None,
+    }
+}
+
+fn bit_or(a: &Object, b: &Object) -> Option<Object> {
+    match (a, b) {
+        (Object::Integer(a), Object::Integer(b)) => Some(Object::Integer(a | b)),
+        (Object::Bool(a), Object::Bool(b)) => Some(Object::Bool(a | b)),
+        _ => None,
+    }
+}
+
+fn bit_xor(a: &Object, b: &Object) -> Option<Object> {
+    match (a, b) {
+        (Object::Integer(a), Object::Integer(b)) => Some(Object::Integer(a ^ b)),
+        (Object::Bool(a), Object::Bool(b)) => Some(Object::Bool(a ^ b)),
+        _ => None,
+    }
+}
+
+fn shift_left(a: &Object, b: &Object) -> Option<Object> {
+    match (a, b) {
+        (Object::Integer(a), Object::Integer(b)) => Some(Object::Integer(a << b)),
+        _ => None,
+    }
+}
+
+fn shift_right(a: &Object, b: &Object) -> Option<Object> {
+    match (a, b) {
+        (Object::Integer(a), Object::Integer(b)) => Some(Object::Integer(a >> b)),
+        _ => None,
+    }
+}
+
+fn range(a: &Object, b: &Object) -> Option<Object> {
+    match (a, b) {
+        (Object::Integer(a), Object::Integer(b)) => Some(Object::Range(*a, *b)),
+        _ => None,
+    }
+}
+
+fn range_inclusive(a: &Object, b: &Object) -> Option<Object> {
+    match (a, b) {
+        (Object::Integer(a), Object::Integer(b)) => Some(Object::Range(*a, b + 1)),
         _ => None,
     }
 }
@@ -45,7 +141,7 @@ fn equal(a: &Object, b: &Object) -> Option<Object> {
     match (a, b) {
         (Object::Integer(a), Object::Integer(b)) => Some(Object::Bool(a == b)),
         (Object::Bool(a), Object::Bool(b)) => Some(Object::Bool(a == b)),
-        _ => None,
+        _ => as_floats(a, b).map(|(a, b)| Object::Bool(a == b)),
     }
 }
 
@@ -53,35 +149,102 @@ fn not_equal(a: &Object, b: &Object) -> Option<Object> {
     match (a, b) {
         (Object::Integer(a), Object::Integer(b)) => Some(Object::Bool(a != b)),
         (Object::Bool(a), Object::Bool(b)) => Some(Object::Bool(a != b)),
-        _ => None,
+        _ => as_floats(a, b).map(|(a, b)| Object::Bool(a != b)),
     }
 }
 
 fn less_than(a: &Object, b: &Object) -> Option<Object> {
     match (a, b) {
         (Object::Integer(a), Object::Integer(b)) => Some(Object::Bool(a < b)),
-        _ => None,
+        _ => as_floats(a, b).map(|(a, b)| Object::Bool(a < b)),
     }
 }
 
 fn greater_than(a: &Object, b: &Object) -> Option<Object> {
     match (a, b) {
         (Object::Integer(a), Object::Integer(b)) => Some(Object::Bool(a > b)),
-        _ => None,
+        _ => as_floats(a, b).map(|(a, b)| Object::Bool(a > b)),
     }
 }
 
 fn less_than_equal(a: &Object, b: &Object) -> Option<Object> {
     match (a, b) {
         (Object::Integer(a), Object::Integer(b)) => Some(Object::Bool(a <= b)),
-        _ => None,
+        _ => as_floats(a, b).map(|(a, b)| Object::Bool(a <= b)),
     }
 }
 
 fn greater_than_equal(a: &Object, b: &Object) -> Option<Object> {
     match (a, b) {
         (Object::Integer(a), Object::Integer(b)) => Some(Object::Bool(a >= b)),
-        _ => None,
+        _ => as_floats(a, b).map(|(a, b)| Object::Bool(a >= b)),
+    }
+}
+
+// Integer operations that would panic are reported as errors before calling
+// the operation
+fn check_operands(
+    operation: &InfixOperation,
+    left: &Object,
+    right: &Object,
+) -> Result<(), EvaluationError> {
+    let (a, b) = match (left, right) {
+        (Object::Integer(a), Object::Integer(b)) => (*a, *b),
+        _ => return Ok(()),
+    };
+
+    let overflows = match operation {
+        InfixOperation::Division | InfixOperation::Modulo if b == 0 => {
+            return Err(EvaluationError::DivisionByZero {
+                operation: operation.clone(),
+            })
+        }
+        InfixOperation::ShiftLeft | InfixOperation::ShiftRight if !(0..64).contains(&b) => {
+            return Err(EvaluationError::InvalidShift { count: b })
+        }
+        InfixOperation::Sum => a.checked_add(b).is_none(),
+        InfixOperation::Subtraction => a.checked_sub(b).is_none(),
+        InfixOperation::Product => a.checked_mul(b).is_none(),
+        InfixOperation::Division => a.checked_div(b).is_none(),
+        InfixOperation::Modulo => a.checked_rem(b).is_none(),
+        InfixOperation::RangeInclusive => b == i64::MAX,
+        InfixOperation::Power if b >= 0 => b > u32::MAX as i64 || a.checked_pow(b as u32).is_none(),
+        _ => false,
+    };
+
+    if overflows {
+        return Err(EvaluationError::IntegerOverflow {
+            operation: operation.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+fn eval_bool(env: &Rc<RefCell<Environment>>, expression: &Expression) -> Result<bool, Signal> {
+    match eval_expression(env, expression)? {
+        Object::Bool(v) => Ok(v),
+        v => Err(EvaluationError::UnexpectedType {
+            value: v,
+            expected: "bool",
+        }
+        .located(expression.span())
+        .into()),
+    }
+}
+
+// `&&` and `||` only evaluate the right operand when the left one does not
+// decide the result
+fn eval_logical(
+    env: &Rc<RefCell<Environment>>,
+    operation: &InfixOperation,
+    left: &Expression,
+    right: &Expression,
+) -> Result<Object, Signal> {
+    match (operation, eval_bool(env, left)?) {
+        (InfixOperation::And, false) => Ok(Object::Bool(false)),
+        (InfixOperation::Or, true) => Ok(Object::Bool(true)),
+        _ => Ok(Object::Bool(eval_bool(env, right)?)),
     }
 }
 
@@ -90,8 +253,26 @@ pub fn eval(
     operation: &InfixOperation,
     left: &Expression,
     right: &Expression,
+) -> Result<Object, Signal> {
+    if let InfixOperation::And | InfixOperation::Or = operation {
+        return eval_logical(env, operation, left, right);
+    }
+
+    let left_v = eval_expression(env, left)?;
+    let right_v = eval_expression(env, right)?;
+    apply(operation, left_v, right_v).map_err(Signal::from)
+}
+
+/// Applies an operation to values that are already evaluated. The logical
+/// operators are applied to booleans, without short-circuiting.
+pub fn apply(
+    operation: &InfixOperation,
+    left_v: Object,
+    right_v: Object,
 ) -> Result<Object, EvaluationError> {
     let func: InfixFn = match operation {
+        InfixOperation::And => and,
+        InfixOperation::Or => or,
         InfixOperation::Sum => sum,
         InfixOperation::Subtraction => subtraction,
         InfixOperation::Division => division,
@@ -102,10 +283,18 @@ pub fn eval(
         InfixOperation::LessThanEqual => less_than_equal,
         InfixOperation::GreaterThanEqual => greater_than_equal,
         InfixOperation::NotEqual => not_equal,
+        InfixOperation::Modulo => modulo,
+        InfixOperation::Power => power,
+        InfixOperation::BitAnd => bit_and,
+        InfixOperation::BitOr => bit_or,
+        InfixOperation::BitXor => bit_xor,
+        InfixOperation::ShiftLeft => shift_left,
+        InfixOperation::ShiftRight => shift_right,
+        InfixOperation::Range => range,
+        InfixOperation::RangeInclusive => range_inclusive,
     };
 
-    let left_v = eval_expression(env, left)?;
-    let right_v = eval_expression(env, right)?;
+    check_operands(operation, &left_v, &right_v)?;
 
     match func(&left_v, &right_v) {
         Some(v) => Ok(v),
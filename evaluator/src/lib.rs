@@ -1,13 +1,20 @@
 pub mod object;
 
 mod array;
+mod assign;
+mod binding;
 mod hashmap;
 mod builtin;
 mod condition;
+mod diagnostics;
 mod env;
 mod function;
 mod infix;
+mod loops;
+mod pattern;
 mod prefix;
+mod range;
+mod string;
 
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -16,6 +23,28 @@ pub use env::Environment;
 pub use builtin::set_builtins_to_env;
 use object::Object;
 use parser::ast::{Expression, InfixOperation, PrefixOperation, Statement};
+use parser::Span;
+
+/// Number of arguments a function takes, `max` is `None` when it takes any
+/// number of them
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub struct Arity {
+    pub min: usize,
+    pub max: Option<usize>,
+}
+
+impl Arity {
+    pub const fn exactly(count: usize) -> Self {
+        Arity {
+            min: count,
+            max: Some(count),
+        }
+    }
+
+    pub fn accepts(&self, count: usize) -> bool {
+        count >= self.min && self.max.is_none_or(|max| count <= max)
+    }
+}
 
 #[derive(PartialEq, Debug)]
 pub enum EvaluationError {
@@ -38,7 +67,7 @@ pub enum EvaluationError {
     },
     IndexOutOfBounds {
         value: Object,
-        index: usize,
+        index: i64,
     },
     NotHashable {
         value: Object,
@@ -50,87 +79,271 @@ pub enum EvaluationError {
         value: Object,
         index: Option<Object>,
     },
+    DivisionByZero {
+        operation: InfixOperation,
+    },
+    InvalidShift {
+        count: i64,
+    },
+    IntegerOverflow {
+        operation: InfixOperation,
+    },
+    PrefixIntegerOverflow {
+        operation: PrefixOperation,
+    },
+    // `suggestion` is a visible variable with a similar name, if there is one
+    UndefinedVariable {
+        name: String,
+        suggestion: Option<String>,
+    },
+    // A destructuring `let` with `expected` elements, or at least `expected`
+    // when it has a `...rest`, was given an array of `found` elements
+    ArrayLengthMismatch {
+        expected: usize,
+        found: usize,
+        rest: bool,
+    },
+    MissingKey {
+        key: String,
+    },
+    NoMatchingArm {
+        value: Object,
+    },
+    UnknownKeywordArgument {
+        name: String,
+    },
+    // A keyword argument for a parameter that was also given positionally,
+    // or twice as a keyword
+    DuplicateArgument {
+        name: String,
+    },
+    // `function` is the name used in the call, if the function was called
+    // through a variable
+    WrongArgumentCount {
+        expected: Arity,
+        got: usize,
+        function: Option<String>,
+    },
+    // Wraps an error with the span of the innermost expression that caused it
+    Located {
+        error: Box<EvaluationError>,
+        span: Span,
+    },
+}
+
+impl EvaluationError {
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            EvaluationError::Located { span, .. } => Some(*span),
+            _ => None,
+        }
+    }
+
+    /// Returns the error without the location information.
+    pub fn without_location(self) -> EvaluationError {
+        match self {
+            EvaluationError::Located { error, .. } => *error,
+            e => e,
+        }
+    }
+
+    fn located(self, span: Span) -> EvaluationError {
+        match self {
+            EvaluationError::Located { .. } => self,
+            e => EvaluationError::Located {
+                error: Box::new(e),
+                span,
+            },
+        }
+    }
+}
+
+/// Why the evaluation of a statement or expression stopped before its end:
+/// an error, or a `return`, `break` or `continue` on its way to the enclosing
+/// function or loop that handles it.
+#[derive(Debug)]
+enum Signal {
+    Error(EvaluationError),
+    Return(Object),
+    Break,
+    Continue,
+}
+
+impl From<EvaluationError> for Signal {
+    fn from(error: EvaluationError) -> Self {
+        Signal::Error(error)
+    }
+}
+
+impl Signal {
+    fn located(self, span: Span) -> Signal {
+        match self {
+            Signal::Error(e) => Signal::Error(e.located(span)),
+            s => s,
+        }
+    }
 }
 
 fn eval_expression(
     env: &Rc<RefCell<Environment>>,
     expression: &Expression,
-) -> Result<Object, EvaluationError> {
-    match expression {
-        Expression::IntegerLiteral { value } => Ok(Object::Integer(*value)),
-        Expression::StringLiteral { value } => Ok(Object::Str(value.clone())),
-        Expression::Array { array } => array::eval_array(env, array),
-        Expression::HashMap { pairs } => hashmap::eval_hashmap(env, pairs),
-        Expression::Index { array, index } =>  {
-    let array_v = eval_expression(env, array)?;
-    let index_v = eval_expression(env, index)?;
-
-    match array_v {
-        Object::Array(arr) => array::eval_indexing(arr, index_v),
-        Object::HashMap(hm) => hashmap::eval_indexing(hm, index_v),
+) -> Result<Object, Signal> {
+    eval_expression_inner(env, expression).map_err(|e| e.located(expression.span()))
+}
+
+fn eval_indexing(collection: Object, index: Object) -> Result<Object, EvaluationError> {
+    match collection {
+        Object::Array(arr) => array::eval_indexing(arr, index),
+        Object::Range(start, end) => range::eval_indexing(start, end, index),
+        Object::HashMap(hm) => hashmap::eval_indexing(hm, index),
         v => Err(EvaluationError::NotIndexable { value: v, index: None }),
     }
-        },
-        Expression::Boolean { value } => Ok(Object::Bool(*value)),
-        Expression::IdentifierExpression { identifier } => Ok(Environment::get_rr(env, identifier)),
+}
+
+fn eval_expression_inner(
+    env: &Rc<RefCell<Environment>>,
+    expression: &Expression,
+) -> Result<Object, Signal> {
+    match expression {
+        Expression::IntegerLiteral { value, .. } => Ok(Object::Integer(*value)),
+        Expression::FloatLiteral { value, .. } => Ok(Object::Float(*value)),
+        Expression::StringLiteral { value, .. } => Ok(Object::Str(value.clone())),
+        Expression::InterpolatedString { parts, .. } => string::eval_interpolation(env, parts),
+        Expression::Array { array, .. } => array::eval_array(env, array),
+        Expression::HashMap { pairs, .. } => hashmap::eval_hashmap(env, pairs),
+        Expression::Index { array, index, .. } => {
+            let array_v = eval_expression(env, array)?;
+            let index_v = eval_expression(env, index)?;
+            eval_indexing(array_v, index_v).map_err(Signal::from)
+        }
+        Expression::Boolean { value, .. } => Ok(Object::Bool(*value)),
+        Expression::IdentifierExpression { identifier, .. } => {
+            lookup_variable(env, identifier).map_err(Signal::from)
+        }
         Expression::InfixExpression {
             operation,
             right,
             left,
+            ..
         } => infix::eval(env, operation, left, right),
         Expression::IfExpression {
             condition,
             consequence,
             alternative,
+            ..
         } => condition::eval(env, condition, consequence, alternative),
-        Expression::PrefixExpression { operation, right } => prefix::eval(env, operation, right),
-        Expression::FunctionExpression { arguments, body } => {
-            function::eval_function(env, arguments, body)
-        }
+        Expression::MatchExpression { value, arms, .. } => pattern::eval_match(env, value, arms),
+        Expression::PrefixExpression {
+            operation, right, ..
+        } => prefix::eval(env, operation, right),
+        Expression::FunctionExpression {
+            arguments,
+            rest,
+            body,
+            ..
+        } => function::eval_function(env, arguments, rest, body),
         Expression::CallExpression {
             arguments,
+            keyword_arguments,
             function,
-        } => function::eval_call(env, function, arguments),
+            ..
+        } => function::eval_call(env, function, arguments, keyword_arguments),
     }
 }
 
+fn undefined_variable(env: &Rc<RefCell<Environment>>, name: &str) -> EvaluationError {
+    EvaluationError::UndefinedVariable {
+        name: name.to_owned(),
+        suggestion: env.borrow().similar_name(name),
+    }
+}
+
+fn lookup_variable(env: &Rc<RefCell<Environment>>, name: &str) -> Result<Object, EvaluationError> {
+    Environment::get_rr(env, name).ok_or_else(|| undefined_variable(env, name))
+}
+
+// Returns the value of the statement if it is the value of its block
 fn eval_statement(
     env: &Rc<RefCell<Environment>>,
     statement: &Statement,
-) -> Result<Option<Object>, EvaluationError> {
+) -> Result<Option<Object>, Signal> {
     match statement {
-        Statement::ReturnStatement { expression } => match eval_expression(env, expression) {
-            Err(e) => Err(e),
-            Ok(v) => Ok(Some(v)),
-        },
+        Statement::ReturnStatement { expression, .. } => {
+            eval_expression(env, expression).and_then(|v| Err(Signal::Return(v)))
+        }
         Statement::LetStatement {
-            identifier,
+            binding,
             expression,
+            span,
         } => {
             let val = eval_expression(env, expression)?;
-            Environment::set_rr(env, identifier.clone(), val);
-            Ok(None)
+            binding::bind(env, binding, val)
+                .map(|_| None)
+                .map_err(|e| Signal::from(e.located(*span)))
         }
-        Statement::ExpressionStatement { expression } => {
-            eval_expression(env, expression)?;
-            Ok(None)
+        Statement::ExpressionStatement { expression, .. } => {
+            eval_expression(env, expression).map(|_| None)
         }
+        Statement::BlockValueStatement { expression, .. } => {
+            eval_expression(env, expression).map(Some)
+        }
+        Statement::AssignStatement {
+            identifier,
+            operation,
+            expression,
+            span,
+        } => assign::eval_assign(env, identifier, operation, expression)
+            .map(|_| None)
+            .map_err(|e| e.located(*span)),
+        Statement::IndexAssignStatement {
+            collection,
+            index,
+            operation,
+            expression,
+            span,
+        } => assign::eval_index_assign(env, collection, index, operation, expression)
+            .map(|_| None)
+            .map_err(|e| e.located(*span)),
+        Statement::WhileStatement {
+            condition, body, ..
+        } => loops::eval_while(env, condition, body).map(|_| None),
+        Statement::ForStatement {
+            key,
+            value,
+            iterable,
+            body,
+            ..
+        } => loops::eval_for(env, key, value, iterable, body).map(|_| None),
+        Statement::BreakStatement { .. } => Err(Signal::Break),
+        Statement::ContinueStatement { .. } => Err(Signal::Continue),
     }
 }
 
+// Evaluates the statements of a block, whose value is the one of its last
+// expression if it has no `;`, or null otherwise
+fn eval_block(env: &Rc<RefCell<Environment>>, statements: &[Statement]) -> Result<Object, Signal> {
+    for statement in statements {
+        if let Some(v) = eval_statement(env, statement)? {
+            return Ok(v);
+        }
+    }
+
+    Ok(Object::Null)
+}
+
+/// Evaluates the statements of a program. A `return` outside of any function
+/// ends the program with its value.
 pub fn eval_statements(
     env: &Rc<RefCell<Environment>>,
     statements: &[Statement],
 ) -> Result<Object, EvaluationError> {
-    for statement in statements {
-        match eval_statement(env, statement) {
-            Err(e) => return Err(e),
-            Ok(Some(v)) => return Ok(v),
-            Ok(None) => {}
+    match eval_block(env, statements) {
+        Ok(v) | Err(Signal::Return(v)) => Ok(v),
+        Err(Signal::Error(e)) => Err(e),
+        Err(Signal::Break) | Err(Signal::Continue) => {
+            unreachable!("The parser rejects `break` and `continue` outside of a loop")
         }
     }
-
-    Ok(Object::Null)
 }
 
 pub fn new_environment() ->Rc<RefCell<Environment>> {
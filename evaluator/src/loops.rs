@@ -0,0 +1,105 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use parser::ast::{Expression, Statement};
+
+use super::{
+    env::Environment, eval_block, eval_expression, object::Object, EvaluationError, Signal,
+};
+
+// Runs one iteration of a loop body. Returns false if the loop has to stop
+// because of a `break`.
+fn eval_body(env: &Rc<RefCell<Environment>>, body: &[Statement]) -> Result<bool, Signal> {
+    match eval_block(env, body) {
+        Ok(_) | Err(Signal::Continue) => Ok(true),
+        Err(Signal::Break) => Ok(false),
+        Err(s) => Err(s),
+    }
+}
+
+pub fn eval_while(
+    env: &Rc<RefCell<Environment>>,
+    condition: &Expression,
+    body: &[Statement],
+) -> Result<(), Signal> {
+    loop {
+        match eval_expression(env, condition)? {
+            Object::Bool(true) => {}
+            Object::Bool(false) => return Ok(()),
+            v => {
+                return Err(EvaluationError::UnexpectedType {
+                    value: v,
+                    expected: "bool",
+                }
+                .located(condition.span())
+                .into())
+            }
+        }
+
+        if !eval_body(env, body)? {
+            return Ok(());
+        }
+    }
+}
+
+type Pairs = Box<dyn Iterator<Item = (Object, Object)>>;
+
+// Pairs of key and value of the elements of an iterable object: the index and
+// the item of arrays, strings and ranges, and the entries of hashmaps
+fn iteration_pairs(iterable: Object) -> Result<Pairs, Object> {
+    let indexed = |(i, v)| (Object::Integer(i as i64), v);
+
+    match iterable {
+        Object::Array(array) => Ok(Box::new(array.into_iter().enumerate().map(indexed))),
+        Object::Str(string) => Ok(Box::new(
+            string
+                .chars()
+                .map(|c| Object::Str(c.to_string()))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .enumerate()
+                .map(indexed),
+        )),
+        Object::Range(start, end) => Ok(Box::new(
+            (start..end).map(Object::Integer).enumerate().map(indexed),
+        )),
+        Object::HashMap(hashmap) => Ok(Box::new(hashmap.into_iter())),
+        v => Err(v),
+    }
+}
+
+pub fn eval_for(
+    env: &Rc<RefCell<Environment>>,
+    key: &Option<String>,
+    value: &str,
+    iterable: &Expression,
+    body: &[Statement],
+) -> Result<(), Signal> {
+    let iterable_v = eval_expression(env, iterable)?;
+    let is_hashmap = matches!(iterable_v, Object::HashMap(_));
+    let pairs = iteration_pairs(iterable_v).map_err(|v| {
+        EvaluationError::UnexpectedType {
+            value: v,
+            expected: "array, hashmap, string or range",
+        }
+        .located(iterable.span())
+    })?;
+
+    for (k, v) in pairs {
+        let body_env = Rc::new(RefCell::new(Environment::with_parent(env)));
+        match key {
+            Some(key) => {
+                Environment::set_rr(&body_env, key.clone(), k);
+                Environment::set_rr(&body_env, value.to_owned(), v);
+            }
+            None if is_hashmap => Environment::set_rr(&body_env, value.to_owned(), k),
+            None => Environment::set_rr(&body_env, value.to_owned(), v),
+        }
+
+        if !eval_body(&body_env, body)? {
+            break;
+        }
+    }
+
+    Ok(())
+}
@@ -3,22 +3,52 @@ use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use std::rc::Rc;
 
-use parser::ast::Statement;
+use parser::ast::{Parameter, Statement};
 
 use super::env::Environment;
 
+/// A function created by a `fn` expression
+pub struct Function {
+    pub parameters: Vec<Parameter>,
+    pub rest: Option<String>,
+    pub body: Vec<Statement>,
+    // The environment where the function was defined
+    pub env: Rc<RefCell<Environment>>,
+}
+
 #[derive(Clone)]
 pub enum Object {
     Null,
     Integer(i64),
+    Float(f64),
     Bool(bool),
     Str(String),
     Array(Vec<Object>),
+    // Integers from the first value to the second one, which is not included.
+    // They are only generated when needed.
+    Range(i64, i64),
     HashMap(HashMap<Object, Object>),
-    Function(Vec<String>, Vec<Statement>, Rc<RefCell<Environment>>),
+    Function(Rc<Function>),
     BuiltInFunction(String),
 }
 
+impl Object {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Object::Null => "null",
+            Object::Integer(_) => "integer",
+            Object::Float(_) => "float",
+            Object::Bool(_) => "bool",
+            Object::Str(_) => "string",
+            Object::Array(_) => "array",
+            Object::Range(_, _) => "range",
+            Object::HashMap(_) => "hashmap",
+            Object::Function(_) => "function",
+            Object::BuiltInFunction(_) => "builtin function",
+        }
+    }
+}
+
 impl Hash for Object {
     fn hash<H: Hasher>(&self, state: &mut H) {
         match self {
@@ -27,9 +57,11 @@ impl Hash for Object {
             Object::Bool(v) => v.hash(state),
             Object::Str(v) => v.hash(state),
 
+            Object::Float(_) => unreachable!("Should not atempt to calculate hash of float"),
             Object::Array(_) => unreachable!("Should not atempt to calculate hash of array"),
+            Object::Range(_, _) => unreachable!("Should not atempt to calculate hash of range"),
             Object::HashMap(_) => unreachable!("Should not atempt to calculate hash of hashmap"),
-            Object::Function(_, _, _) => {
+            Object::Function(_) => {
                 unreachable!("Should not atempt to calculate hash of functioj")
             }
             Object::BuiltInFunction(_) => {
@@ -49,9 +81,11 @@ impl PartialEq for Object {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Object::Integer(v1), Object::Integer(v2)) => v1 == v2,
+            (Object::Float(v1), Object::Float(v2)) => v1 == v2,
             (Object::Bool(v1), Object::Bool(v2)) => v1 == v2,
             (Object::Str(v1), Object::Str(v2)) => v1 == v2,
             (Object::HashMap(v1), Object::HashMap(v2)) => keys_match(v1, v2),
+            (Object::Range(s1, e1), Object::Range(s2, e2)) => s1 == s2 && e1 == e2,
             (Object::Array(v1), Object::Array(v2)) => {
                 if v1.len() != v2.len() {
                     return false;
@@ -73,11 +107,20 @@ impl std::fmt::Debug for Object {
         match self {
             Object::Null => f.debug_tuple("Null").finish(),
             Object::Integer(v) => f.debug_tuple("Integer").field(v).finish(),
+            Object::Float(v) => f.debug_tuple("Float").field(v).finish(),
             Object::Bool(v) => f.debug_tuple("Bool").field(v).finish(),
             Object::Str(v) => f.debug_tuple("Str").field(v).finish(),
             Object::Array(v) => f.debug_list().entries(v.iter()).finish(),
+            Object::Range(start, end) => f.debug_tuple("Range").field(start).field(end).finish(),
             Object::HashMap(v) => f.debug_map().entries(v.iter()).finish(),
-            Object::Function(args, _, _) => f.debug_tuple("Function").field(args).finish(),
+            Object::Function(function) => {
+                let names = function
+                    .parameters
+                    .iter()
+                    .map(|p| &p.name)
+                    .collect::<Vec<_>>();
+                f.debug_tuple("Function").field(&names).finish()
+            }
             Object::BuiltInFunction(name) => f.debug_tuple("BuiltInFunction").field(name).finish(),
         }
     }
@@ -102,13 +145,21 @@ impl std::fmt::Display for Object {
         match self {
             Object::Null => write!(f, "NULL"),
             Object::Integer(v) => write!(f, "{}", v),
+            // Debug keeps the decimal point of integral values: `2.0` instead of `2`
+            Object::Float(v) => write!(f, "{:?}", v),
             Object::Bool(v) => write!(f, "{}", v),
             Object::Str(v) => write!(f, "{}", v),
             Object::HashMap(_) => write!(f, "HashMap"), // TODO
             Object::Array(v) => display_array(v, f),
-            Object::Function(args, _, _) => {
-                write!(f, "]")?;
-                display_array(args, f)
+            Object::Range(start, end) => write!(f, "{}..{}", start, end),
+            Object::Function(function) => {
+                let names = function
+                    .parameters
+                    .iter()
+                    .map(|p| p.name.clone())
+                    .chain(function.rest.iter().map(|rest| format!("...{}", rest)))
+                    .collect::<Vec<_>>();
+                write!(f, "fn({})", names.join(", "))
             }
             Object::BuiltInFunction(name) => write!(f, "builtin({})", name),
         }
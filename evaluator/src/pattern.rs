@@ -0,0 +1,100 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use parser::ast::{Expression, MatchArm, Pattern};
+
+use super::{env::Environment, eval_expression, object::Object, EvaluationError, Signal};
+
+pub fn eval_match(
+    env: &Rc<RefCell<Environment>>,
+    value: &Expression,
+    arms: &[MatchArm],
+) -> Result<Object, Signal> {
+    let value = eval_expression(env, value)?;
+
+    for arm in arms {
+        let mut bound = Vec::new();
+        if !matches(env, &arm.pattern, &value, &mut bound)? {
+            continue;
+        }
+
+        let arm_env = Rc::new(RefCell::new(Environment::with_parent(env)));
+        for (name, v) in bound {
+            Environment::set_rr(&arm_env, name, v);
+        }
+
+        if let Some(guard) = &arm.guard {
+            match eval_expression(&arm_env, guard)? {
+                Object::Bool(true) => {}
+                Object::Bool(false) => continue,
+                v => {
+                    return Err(EvaluationError::UnexpectedType {
+                        value: v,
+                        expected: "bool",
+                    }
+                    .located(guard.span())
+                    .into())
+                }
+            }
+        }
+
+        return eval_expression(&arm_env, &arm.body);
+    }
+
+    Err(EvaluationError::NoMatchingArm { value }.into())
+}
+
+// Checks if `value` matches `pattern`, collecting the names it binds
+fn matches(
+    env: &Rc<RefCell<Environment>>,
+    pattern: &Pattern,
+    value: &Object,
+    bound: &mut Vec<(String, Object)>,
+) -> Result<bool, Signal> {
+    match pattern {
+        Pattern::Wildcard => Ok(true),
+        Pattern::Literal { value: literal } => Ok(eval_expression(env, literal)? == *value),
+        Pattern::Identifier { name } => {
+            bound.push((name.clone(), value.clone()));
+            Ok(true)
+        }
+        Pattern::Array { elements, rest } => match value {
+            Object::Array(arr) => matches_array(env, elements, rest, arr, bound),
+            _ => Ok(false),
+        },
+        Pattern::HashMap { pairs } => match value {
+            Object::HashMap(hm) => {
+                for (key, pattern) in pairs {
+                    match hm.get(&Object::Str(key.clone())) {
+                        Some(v) if matches(env, pattern, v, bound)? => {}
+                        _ => return Ok(false),
+                    }
+                }
+                Ok(true)
+            }
+            _ => Ok(false),
+        },
+    }
+}
+
+fn matches_array(
+    env: &Rc<RefCell<Environment>>,
+    elements: &[Pattern],
+    rest: &Option<String>,
+    arr: &[Object],
+    bound: &mut Vec<(String, Object)>,
+) -> Result<bool, Signal> {
+    if arr.len() < elements.len() || (rest.is_none() && arr.len() > elements.len()) {
+        return Ok(false);
+    }
+
+    for (pattern, v) in elements.iter().zip(arr) {
+        if !matches(env, pattern, v, bound)? {
+            return Ok(false);
+        }
+    }
+    if let Some(rest) = rest {
+        bound.push((rest.clone(), Object::Array(arr[elements.len()..].to_vec())));
+    }
+    Ok(true)
+}
@@ -3,27 +3,28 @@ use std::rc::Rc;
 
 use parser::ast::{Expression, PrefixOperation};
 
-use super::{env::Environment, eval_expression, object::Object, EvaluationError};
+use super::{env::Environment, eval_expression, object::Object, EvaluationError, Signal};
 
-fn eval_negative(
-    env: &Rc<RefCell<Environment>>,
-    right: &Expression,
-) -> Result<Object, EvaluationError> {
+fn eval_negative(env: &Rc<RefCell<Environment>>, right: &Expression) -> Result<Object, Signal> {
     let v = eval_expression(env, right)?;
 
     match v {
-        Object::Integer(a) => Ok(Object::Integer(-a)),
+        Object::Integer(a) => a.checked_neg().map(Object::Integer).ok_or_else(|| {
+            EvaluationError::PrefixIntegerOverflow {
+                operation: PrefixOperation::Negative,
+            }
+            .into()
+        }),
+        Object::Float(a) => Ok(Object::Float(-a)),
         v => Err(EvaluationError::PrefixOperationNotImplemented {
             operation: PrefixOperation::Negative,
             right: v,
-        }),
+        }
+        .into()),
     }
 }
 
-fn eval_negate(
-    env: &Rc<RefCell<Environment>>,
-    right: &Expression,
-) -> Result<Object, EvaluationError> {
+fn eval_negate(env: &Rc<RefCell<Environment>>, right: &Expression) -> Result<Object, Signal> {
     let v = eval_expression(env, right)?;
 
     match v {
@@ -31,7 +32,21 @@ fn eval_negate(
         v => Err(EvaluationError::PrefixOperationNotImplemented {
             operation: PrefixOperation::Negate,
             right: v,
-        }),
+        }
+        .into()),
+    }
+}
+
+fn eval_bit_not(env: &Rc<RefCell<Environment>>, right: &Expression) -> Result<Object, Signal> {
+    let v = eval_expression(env, right)?;
+
+    match v {
+        Object::Integer(a) => Ok(Object::Integer(!a)),
+        v => Err(EvaluationError::PrefixOperationNotImplemented {
+            operation: PrefixOperation::BitNot,
+            right: v,
+        }
+        .into()),
     }
 }
 
@@ -39,9 +54,10 @@ pub fn eval(
     env: &Rc<RefCell<Environment>>,
     operation: &PrefixOperation,
     right: &Expression,
-) -> Result<Object, EvaluationError> {
+) -> Result<Object, Signal> {
     match operation {
         PrefixOperation::Negate => eval_negate(env, right),
         PrefixOperation::Negative => eval_negative(env, right),
+        PrefixOperation::BitNot => eval_bit_not(env, right),
     }
 }
@@ -0,0 +1,27 @@
+use super::{object::Object, EvaluationError};
+
+pub fn len(start: i64, end: i64) -> i64 {
+    end.saturating_sub(start).max(0)
+}
+
+pub fn eval_indexing(start: i64, end: i64, index: Object) -> Result<Object, EvaluationError> {
+    match index {
+        Object::Integer(i) => {
+            if i < 0 || i >= len(start, end) {
+                return Err(EvaluationError::IndexOutOfBounds {
+                    value: Object::Range(start, end),
+                    index: i,
+                });
+            }
+            Ok(Object::Integer(start + i))
+        }
+        i => Err(EvaluationError::NotIndexable {
+            value: Object::Range(start, end),
+            index: Some(i),
+        }),
+    }
+}
+
+pub fn to_array(start: i64, end: i64) -> Object {
+    Object::Array((start..end).map(Object::Integer).collect())
+}
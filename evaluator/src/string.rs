@@ -0,0 +1,24 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use parser::ast::StringPart;
+
+use super::{env::Environment, eval_expression, object::Object, Signal};
+
+pub fn eval_interpolation(
+    env: &Rc<RefCell<Environment>>,
+    parts: &[StringPart],
+) -> Result<Object, Signal> {
+    let mut string = String::new();
+
+    for part in parts {
+        match part {
+            StringPart::Literal { value } => string.push_str(value),
+            StringPart::Expression { expression } => {
+                string.push_str(&eval_expression(env, expression)?.to_string())
+            }
+        }
+    }
+
+    Ok(Object::Str(string))
+}
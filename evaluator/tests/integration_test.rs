@@ -1,12 +1,12 @@
-use parser::ast::InfixOperation;
+use parser::ast::{InfixOperation, PrefixOperation};
 use parser::Parser;
 
 use evaluator::object::Object;
-use evaluator::{eval_program, EvaluationError};
+use evaluator::{eval_program, eval_statements, new_environment, Arity, EvaluationError};
 
 fn run(program: &str) -> Result<Object, EvaluationError> {
     let ast = Parser::new(program.chars()).parse_program().unwrap();
-    eval_program(&ast)
+    eval_program(&ast).map_err(EvaluationError::without_location)
 }
 
 #[test]
@@ -121,6 +121,13 @@ fn test_array_index_out() {
             index: 2,
         })
     );
+    assert_eq!(
+        run("[1, 2][-1]"),
+        Err(EvaluationError::IndexOutOfBounds {
+            value: Object::Array(vec![Object::Integer(1), Object::Integer(2)]),
+            index: -1,
+        })
+    );
 }
 
 #[test]
@@ -270,6 +277,28 @@ fn test_if_else_expression_false() {
     assert_eq!(run(program), Ok(Object::Str(String::from("cool"))));
 }
 
+#[test]
+fn test_else_if_expression() {
+    let program = "
+        let sign = fn(x) {
+            if (x > 0) { \"positive\" } else if (x < 0) { \"negative\" } else { \"zero\" }
+        };
+        let grade = fn(x) { if (x >= 90) { \"A\" } else if (x >= 80) { \"B\" } };
+        [sign(5), sign(-5), sign(0), grade(95), grade(85), grade(10)]
+    ";
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(vec![
+            Object::Str(String::from("positive")),
+            Object::Str(String::from("negative")),
+            Object::Str(String::from("zero")),
+            Object::Str(String::from("A")),
+            Object::Str(String::from("B")),
+            Object::Null,
+        ]))
+    );
+}
+
 #[test]
 fn test_if_number() {
     let program = "if (1403) { 3 }";
@@ -400,8 +429,9 @@ fn test_call_null() {
         ";
     assert_eq!(
         run(program),
-        Err(EvaluationError::NotCallable {
-            value: Object::Null
+        Err(EvaluationError::UndefinedVariable {
+            name: String::from("add"),
+            suggestion: None
         })
     );
 }
@@ -520,7 +550,13 @@ fn test_scope_3() {
         returnC()
     }()
         ";
-    assert_eq!(run(program), Ok(Object::Null));
+    assert_eq!(
+        run(program),
+        Err(EvaluationError::UndefinedVariable {
+            name: String::from("c"),
+            suggestion: None
+        })
+    );
 }
 
 #[test]
@@ -572,3 +608,1168 @@ fn test_fibonacci() {
     ";
     assert_eq!(run(program), Ok(Object::Integer(987)));
 }
+
+#[test]
+fn test_error_location() {
+    let program = "
+    let getFirst = fn (xs) { xs[0] };
+    getFirst(3)
+    ";
+
+    let ast = Parser::new(program.chars()).parse_program().unwrap();
+    let span = eval_program(&ast).unwrap_err().span().unwrap();
+
+    assert_eq!((span.line, span.column, span.end - span.start), (2, 30, 5));
+}
+
+#[test]
+fn test_error_diagnostic() {
+    let program = "let a = [1, 2];\nlen(a) + true";
+
+    let ast = Parser::new(program.chars()).parse_program().unwrap();
+    let rendered = eval_program(&ast).unwrap_err().diagnostic().render(program);
+
+    assert_eq!(
+        rendered,
+        "error: cannot apply `+` to integer and bool
+ --> 2:1
+  |
+2 | len(a) + true
+  | ^^^^^^^^^^^^^
+"
+    );
+}
+
+#[test]
+fn test_error_diagnostic_in_previous_input() {
+    let env = new_environment();
+    let mut source = String::new();
+    let mut eval_line = |line: &str| {
+        let line_number = source.matches('\n').count() + 1;
+        let statements = Parser::starting_at(line.chars(), source.len(), line_number)
+            .parse_program()
+            .unwrap();
+        source.push_str(line);
+        eval_statements(&env, &statements).map_err(|e| e.diagnostic().render(&source))
+    };
+
+    assert!(eval_line("let f = fn(x) { x + true };\n").is_ok());
+    assert_eq!(
+        eval_line("f(1)\n"),
+        Err(String::from(
+            "error: cannot apply `+` to integer and bool
+ --> 1:17
+  |
+1 | let f = fn(x) { x + true };
+  |                 ^^^^^^^^
+"
+        ))
+    );
+}
+
+#[test]
+fn test_unlocated_error_diagnostic() {
+    let error = EvaluationError::DivisionByZero {
+        operation: InfixOperation::Division,
+    };
+
+    assert_eq!(
+        error.diagnostic().render("1 / 0"),
+        "error: cannot apply `/` with a divisor of zero
+ --> 1:1
+  |
+1 | 1 / 0
+  | ^
+"
+    );
+}
+
+#[test]
+fn test_string_escapes() {
+    let program = r#"len("a\tb\"") + len(r"\n")"#;
+    assert_eq!(run(program), Ok(Object::Integer(6)));
+}
+
+#[test]
+fn test_string_interpolation() {
+    let program = r#"
+        let name = "Ana";
+        let xs = [1, 2, 3];
+        "hello ${name}, you have ${len(xs)} items: ${xs} ${len(xs) > 2} ${"${1 + 1}"}"
+    "#;
+    assert_eq!(
+        run(program),
+        Ok(Object::Str(String::from(
+            "hello Ana, you have 3 items: [1, 2, 3] true 2"
+        )))
+    );
+}
+
+#[test]
+fn test_string_interpolation_error() {
+    let program = r#""${-"a"}""#;
+    assert!(run(program).is_err());
+}
+
+#[test]
+fn test_float_arithmetic() {
+    assert_eq!(run("1.5 + 2.25"), Ok(Object::Float(3.75)));
+    assert_eq!(run("7 / 2.0"), Ok(Object::Float(3.5)));
+    assert_eq!(run("2.5 * 2 - 1"), Ok(Object::Float(4.0)));
+    assert_eq!(run("-1e-3"), Ok(Object::Float(-0.001)));
+}
+
+#[test]
+fn test_float_comparison() {
+    assert_eq!(run("1 < 1.5"), Ok(Object::Bool(true)));
+    assert_eq!(run("2.0 == 2"), Ok(Object::Bool(true)));
+    assert_eq!(run("0.5 >= 1"), Ok(Object::Bool(false)));
+}
+
+#[test]
+fn test_float_display() {
+    assert_eq!(
+        run(r#""${2.0} ${0.25} ${[1.5, 2]}""#),
+        Ok(Object::Str(String::from("2.0 0.25 [1.5, 2]")))
+    );
+}
+
+#[test]
+fn test_function_display() {
+    assert_eq!(
+        run(r#""${fn(x) { x }} ${fn() { 1 }} ${fn(a, b = 2, ...rest) { a }}""#),
+        Ok(Object::Str(String::from("fn(x) fn() fn(a, b, ...rest)")))
+    );
+}
+
+#[test]
+fn test_number_conversion_builtins() {
+    assert_eq!(run("int(-3.9)"), Ok(Object::Integer(-3)));
+    assert_eq!(run("int(\" 42 \")"), Ok(Object::Integer(42)));
+    assert_eq!(run("float(3)"), Ok(Object::Float(3.0)));
+    assert_eq!(run("float(\"2.5\")"), Ok(Object::Float(2.5)));
+    assert_eq!(
+        run("let xs = [1, 2, 4]; float(1 + 2 + 4) / len(xs)"),
+        Ok(Object::Float(7.0 / 3.0))
+    );
+    assert!(run("int(\"abc\")").is_err());
+    assert!(run("float(true)").is_err());
+}
+
+#[test]
+fn test_float_is_not_hashable() {
+    assert_eq!(
+        run("{1.5: 1}"),
+        Err(EvaluationError::NotHashable {
+            value: Object::Float(1.5)
+        })
+    );
+}
+
+#[test]
+fn test_identifiers_with_digits_and_underscores() {
+    let program = "let user_id = 4; let x2 = 3; let _tmp = user_id * x2; _tmp";
+    assert_eq!(run(program), Ok(Object::Integer(12)));
+}
+
+#[test]
+fn test_logical_operators() {
+    assert_eq!(run("true && false"), Ok(Object::Bool(false)));
+    assert_eq!(run("false || true"), Ok(Object::Bool(true)));
+    assert_eq!(run("1 < 2 && 2 < 3 || false"), Ok(Object::Bool(true)));
+}
+
+#[test]
+fn test_logical_operators_short_circuit() {
+    let program = "let fail = fn() { 1 + true }; [false && fail(), true || fail()]";
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(vec![Object::Bool(false), Object::Bool(true)]))
+    );
+}
+
+#[test]
+fn test_logical_operators_require_bools() {
+    assert_eq!(
+        run("true && 1"),
+        Err(EvaluationError::UnexpectedType {
+            value: Object::Integer(1),
+            expected: "bool"
+        })
+    );
+    assert_eq!(
+        run("1 || true"),
+        Err(EvaluationError::UnexpectedType {
+            value: Object::Integer(1),
+            expected: "bool"
+        })
+    );
+}
+
+#[test]
+fn test_modulo_and_power() {
+    assert_eq!(run("17 % 5"), Ok(Object::Integer(2)));
+    assert_eq!(run("-7 % 3"), Ok(Object::Integer(-1)));
+    assert_eq!(run("7.5 % 2"), Ok(Object::Float(1.5)));
+    assert_eq!(run("2 ** 10"), Ok(Object::Integer(1024)));
+    assert_eq!(run("2 ** 3 ** 2"), Ok(Object::Integer(512)));
+    assert_eq!(run("-2 ** 2"), Ok(Object::Integer(-4)));
+    assert_eq!(run("2 ** -1"), Ok(Object::Float(0.5)));
+    assert_eq!(run("4 ** 0.5"), Ok(Object::Float(2.0)));
+}
+
+#[test]
+fn test_bitwise_operators() {
+    assert_eq!(run("0b1100 & 0b1010"), Ok(Object::Integer(0b1000)));
+    assert_eq!(run("0b1100 | 0b1010"), Ok(Object::Integer(0b1110)));
+    assert_eq!(run("0b1100 ^ 0b1010"), Ok(Object::Integer(0b0110)));
+    assert_eq!(run("1 << 4"), Ok(Object::Integer(16)));
+    assert_eq!(run("-16 >> 2"), Ok(Object::Integer(-4)));
+    assert_eq!(run("~0"), Ok(Object::Integer(-1)));
+    assert_eq!(run("true ^ true"), Ok(Object::Bool(false)));
+}
+
+#[test]
+fn test_arithmetic_errors() {
+    assert_eq!(
+        run("5 % 0"),
+        Err(EvaluationError::DivisionByZero {
+            operation: InfixOperation::Modulo
+        })
+    );
+    assert_eq!(
+        run("5 / (2 - 2)"),
+        Err(EvaluationError::DivisionByZero {
+            operation: InfixOperation::Division
+        })
+    );
+    assert_eq!(
+        run("1 << -1"),
+        Err(EvaluationError::InvalidShift { count: -1 })
+    );
+    assert_eq!(
+        run("1 >> 64"),
+        Err(EvaluationError::InvalidShift { count: 64 })
+    );
+    assert_eq!(
+        run("10 ** 19"),
+        Err(EvaluationError::IntegerOverflow {
+            operation: InfixOperation::Power
+        })
+    );
+    assert_eq!(
+        run("9223372036854775807 + 1"),
+        Err(EvaluationError::IntegerOverflow {
+            operation: InfixOperation::Sum
+        })
+    );
+    assert_eq!(
+        run("-9223372036854775807 - 2"),
+        Err(EvaluationError::IntegerOverflow {
+            operation: InfixOperation::Subtraction
+        })
+    );
+    assert_eq!(
+        run("let a = 4611686018427387904; a *= 2; a"),
+        Err(EvaluationError::IntegerOverflow {
+            operation: InfixOperation::Product
+        })
+    );
+    assert_eq!(
+        run("-(-9223372036854775807 - 1)"),
+        Err(EvaluationError::PrefixIntegerOverflow {
+            operation: PrefixOperation::Negative
+        })
+    );
+    assert_eq!(
+        run("~true"),
+        Err(EvaluationError::PrefixOperationNotImplemented {
+            operation: PrefixOperation::BitNot,
+            right: Object::Bool(true)
+        })
+    );
+}
+
+#[test]
+fn test_while_loop() {
+    let program = "
+        let i = 0;
+        let sum = 0;
+        while (i < 10) {
+            let i = i + 1;
+            if (i % 2 == 0) { continue; };
+            if (i > 7) { break; };
+            let sum = sum + i;
+        }
+        sum
+    ";
+    assert_eq!(run(program), Ok(Object::Integer(16)));
+}
+
+#[test]
+fn test_nested_while_loops() {
+    let program = "
+        let i = 0;
+        let count = 0;
+        while (i < 3) {
+            let i = i + 1;
+            let j = 0;
+            while (true) {
+                let j = j + 1;
+                if (j > i) { break; };
+                let count = count + 1;
+            }
+        }
+        count
+    ";
+    assert_eq!(run(program), Ok(Object::Integer(6)));
+}
+
+#[test]
+fn test_while_condition_must_be_bool() {
+    assert_eq!(
+        run("while (1) { }"),
+        Err(EvaluationError::UnexpectedType {
+            value: Object::Integer(1),
+            expected: "bool"
+        })
+    );
+}
+
+#[test]
+fn test_for_loop_over_array() {
+    let program = "
+        let seen = [];
+        for (i, x in [5, 6, 7]) { seen += [[i, x]]; }
+        seen
+    ";
+    let pair = |i, x| Object::Array(vec![Object::Integer(i), Object::Integer(x)]);
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(vec![pair(0, 5), pair(1, 6), pair(2, 7)]))
+    );
+
+    let program = "
+        let seen = [];
+        for (x in [5, 6, 7]) { if (x == 6) { break; }; seen = push(seen, x); }
+        seen
+    ";
+    assert_eq!(run(program), Ok(Object::Array(vec![Object::Integer(5)])));
+}
+
+#[test]
+fn test_for_loop_over_string() {
+    let program = "
+        let seen = \"\";
+        for (i, c in \"héllo\") { if (i % 2 == 1) { seen += c; }; }
+        seen
+    ";
+    assert_eq!(run(program), Ok(Object::Str(String::from("él"))));
+}
+
+#[test]
+fn test_for_loop_over_hashmap() {
+    let program = "
+        let total = 0;
+        for (k, v in {1: 10, 2: 20}) { total += k * v; }
+        total
+    ";
+    assert_eq!(run(program), Ok(Object::Integer(50)));
+
+    let program = "
+        let total = 0;
+        for (k in {1: 10, 2: 20}) { total += k; }
+        total
+    ";
+    assert_eq!(run(program), Ok(Object::Integer(3)));
+}
+
+#[test]
+fn test_for_loop_over_range() {
+    let program = "
+        let seen = [];
+        for (i in range(2, 6)) { if (i == 4) { continue; }; seen = push(seen, i); }
+        seen
+    ";
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(vec![
+            Object::Integer(2),
+            Object::Integer(3),
+            Object::Integer(5)
+        ]))
+    );
+    assert_eq!(run("for (i in range(3)) { i }"), Ok(Object::Null));
+}
+
+#[test]
+fn test_for_loop_variables_are_scoped_to_the_body() {
+    let program = "let x = 1; for (x in [2, 3]) { let y = x; }; x";
+    assert_eq!(run(program), Ok(Object::Integer(1)));
+
+    let program = "let x = 1; for (x in [2, 3]) { let y = x; }; y";
+    assert_eq!(
+        run(program),
+        Err(EvaluationError::UndefinedVariable {
+            name: String::from("y"),
+            suggestion: None
+        })
+    );
+}
+
+#[test]
+fn test_for_loop_over_non_iterable() {
+    assert_eq!(
+        run("for (x in 5) { }"),
+        Err(EvaluationError::UnexpectedType {
+            value: Object::Integer(5),
+            expected: "array, hashmap, string or range"
+        })
+    );
+}
+
+#[test]
+fn test_range() {
+    assert_eq!(run("1..4"), Ok(Object::Range(1, 4)));
+    assert_eq!(run("1..=4"), Ok(Object::Range(1, 5)));
+    assert_eq!(run("range(3)"), Ok(Object::Range(0, 3)));
+    assert_eq!(
+        run("array(0..=3)"),
+        Ok(Object::Array(
+            (0..=3).map(Object::Integer).collect::<Vec<_>>()
+        ))
+    );
+    assert_eq!(run("array(3..1)"), Ok(Object::Array(vec![])));
+}
+
+#[test]
+fn test_range_len_and_indexing() {
+    assert_eq!(run("len(0..1000000000000)"), Ok(Object::Integer(1_000_000_000_000)));
+    assert_eq!(run("len(5..2)"), Ok(Object::Integer(0)));
+    assert_eq!(run("(10..20)[3]"), Ok(Object::Integer(13)));
+    assert_eq!(
+        run("(10..20)[10]"),
+        Err(EvaluationError::IndexOutOfBounds {
+            value: Object::Range(10, 20),
+            index: 10
+        })
+    );
+    assert_eq!(
+        run("(10..20)[-1]"),
+        Err(EvaluationError::IndexOutOfBounds {
+            value: Object::Range(10, 20),
+            index: -1
+        })
+    );
+}
+
+#[test]
+fn test_for_loop_over_large_range() {
+    let program = "
+        let last = 0;
+        for (i in 0..1000000000000) { last = i; if (i == 3) { break; }; }
+        last
+    ";
+    assert_eq!(run(program), Ok(Object::Integer(3)));
+}
+
+#[test]
+fn test_invalid_range() {
+    assert!(run("1..\"a\"").is_err());
+    assert_eq!(
+        run("0..=9223372036854775807"),
+        Err(EvaluationError::IntegerOverflow {
+            operation: InfixOperation::RangeInclusive
+        })
+    );
+}
+
+#[test]
+fn test_reassignment() {
+    let program = "
+        let total = 0;
+        for (x in 1..=10) {
+            total += x;
+        }
+        let i = 0;
+        while (i < 3) {
+            i = i + 1;
+            total *= 2;
+        }
+        total -= 40;
+        total /= 10;
+        [i, total]
+    ";
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(vec![Object::Integer(3), Object::Integer(40)]))
+    );
+}
+
+#[test]
+fn test_reassignment_updates_the_defining_scope() {
+    let program = "
+        let make_counter = fn() {
+            let count = 0;
+            fn() { count += 1; count }
+        };
+        let counter = make_counter();
+        counter();
+        counter();
+        let other = make_counter();
+        [counter(), other()]
+    ";
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(vec![Object::Integer(3), Object::Integer(1)]))
+    );
+}
+
+#[test]
+fn test_reassignment_of_undefined_variable() {
+    assert_eq!(
+        run("x = 1;"),
+        Err(EvaluationError::UndefinedVariable {
+            name: String::from("x"),
+            suggestion: None
+        })
+    );
+    assert_eq!(
+        run("let x = \"a\"; x -= 1;"),
+        Err(EvaluationError::InfixOperationNotImplemented {
+            operation: InfixOperation::Subtraction,
+            left: Object::Str(String::from("a")),
+            right: Object::Integer(1)
+        })
+    );
+}
+
+#[test]
+fn test_index_assignment() {
+    let program = "
+        let xs = [1, 2, 3];
+        let ys = xs;
+        xs[0] = 10;
+        xs[2] += 5;
+        let h = {\"a\": 1};
+        h[\"b\"] = [0, 0];
+        h[\"b\"][1] = 7;
+        h[\"a\"] -= 1;
+        [xs, ys, h[\"a\"], h[\"b\"]]
+    ";
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(vec![
+            Object::Array(vec![
+                Object::Integer(10),
+                Object::Integer(2),
+                Object::Integer(8)
+            ]),
+            Object::Array(vec![
+                Object::Integer(1),
+                Object::Integer(2),
+                Object::Integer(3)
+            ]),
+            Object::Integer(0),
+            Object::Array(vec![Object::Integer(0), Object::Integer(7)]),
+        ]))
+    );
+}
+
+#[test]
+fn test_index_assignment_errors() {
+    assert_eq!(
+        run("let xs = [1]; xs[1] = 2;"),
+        Err(EvaluationError::IndexOutOfBounds {
+            value: Object::Array(vec![Object::Integer(1)]),
+            index: 1
+        })
+    );
+    assert_eq!(
+        run("let xs = [1]; xs[-1] = 2;"),
+        Err(EvaluationError::IndexOutOfBounds {
+            value: Object::Array(vec![Object::Integer(1)]),
+            index: -1
+        })
+    );
+    assert_eq!(
+        run("let h = {}; h[[1]] = 2;"),
+        Err(EvaluationError::NotHashable {
+            value: Object::Array(vec![Object::Integer(1)])
+        })
+    );
+    assert_eq!(
+        run("let s = \"abc\"; s[0] = \"x\";"),
+        Err(EvaluationError::NotIndexable {
+            value: Object::Str(String::from("abc")),
+            index: None
+        })
+    );
+}
+
+#[test]
+fn test_destructuring_let() {
+    let program = "
+        let [a, [b, c], ...rest] = [1, [2, 3], 4, 5];
+        let [first, ...empty] = [a];
+        let {name, age} = {\"name\": \"Ana\", \"age\": 30, \"city\": \"Lima\"};
+        [a, b, c, rest, first, empty, name, age]
+    ";
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(vec![
+            Object::Integer(1),
+            Object::Integer(2),
+            Object::Integer(3),
+            Object::Array(vec![Object::Integer(4), Object::Integer(5)]),
+            Object::Integer(1),
+            Object::Array(vec![]),
+            Object::Str(String::from("Ana")),
+            Object::Integer(30),
+        ]))
+    );
+}
+
+#[test]
+fn test_destructuring_let_errors() {
+    assert_eq!(
+        run("let [a, b] = [1, 2, 3];"),
+        Err(EvaluationError::ArrayLengthMismatch {
+            expected: 2,
+            found: 3,
+            rest: false
+        })
+    );
+    assert_eq!(
+        run("let [a, b, ...rest] = [1];"),
+        Err(EvaluationError::ArrayLengthMismatch {
+            expected: 2,
+            found: 1,
+            rest: true
+        })
+    );
+    assert_eq!(
+        run("let [a] = 1;"),
+        Err(EvaluationError::UnexpectedType {
+            value: Object::Integer(1),
+            expected: "array"
+        })
+    );
+    assert_eq!(
+        run("let {a} = [1];"),
+        Err(EvaluationError::UnexpectedType {
+            value: Object::Array(vec![Object::Integer(1)]),
+            expected: "hashmap"
+        })
+    );
+    assert_eq!(
+        run("let {name, age} = {\"name\": \"Ana\"};"),
+        Err(EvaluationError::MissingKey {
+            key: String::from("age")
+        })
+    );
+    assert_eq!(
+        run("let [a, [b]] = [1, 2];"),
+        Err(EvaluationError::UnexpectedType {
+            value: Object::Integer(2),
+            expected: "array"
+        })
+    );
+    assert_eq!(
+        EvaluationError::ArrayLengthMismatch {
+            expected: 2,
+            found: 1,
+            rest: true
+        }
+        .to_string(),
+        "expected an array of at least 2 elements, found 1"
+    );
+}
+
+#[test]
+fn test_match_expression() {
+    let program = r#"
+        let describe = fn(v) {
+            match (v) {
+                0 => "zero",
+                -1 => "minus one",
+                "hi" => "greeting",
+                true => "yes",
+                [] => "empty",
+                [x] => "one " + x,
+                [x, ...rest] => "first ${x} of ${len(rest) + 1}",
+                {name, "age": 30} => name + " is thirty",
+                {name} => name,
+                n if n > 100 => "big ${n}",
+                _ => "other",
+            }
+        };
+        [
+            describe(0), describe(-1), describe("hi"), describe(true),
+            describe(101), describe(7), describe([]), describe(["a"]),
+            describe(["a", "b", "c"]), describe({"name": "Ana", "age": 30}),
+            describe({"name": "Bo", "age": 31}), describe(2.5)
+        ]
+    "#;
+    let expected = [
+        "zero",
+        "minus one",
+        "greeting",
+        "yes",
+        "big 101",
+        "other",
+        "empty",
+        "one a",
+        "first a of 3",
+        "Ana is thirty",
+        "Bo",
+        "other",
+    ];
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(
+            expected
+                .iter()
+                .map(|s| Object::Str(String::from(*s)))
+                .collect()
+        ))
+    );
+}
+
+#[test]
+fn test_match_bindings_are_scoped_to_the_arm() {
+    let program = "
+        let x = 1;
+        let y = match ([2, 3]) { [x, y] if x < y => x * 10 + y };
+        [x, y]
+    ";
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(vec![Object::Integer(1), Object::Integer(23)]))
+    );
+    // A guard that fails skips the arm, and a literal only matches its own type
+    assert_eq!(
+        run("match (1.0) { 1 => \"int\", x if x > 5.0 => \"big\", _ => \"float\" }"),
+        Ok(Object::Str(String::from("float")))
+    );
+}
+
+#[test]
+fn test_match_errors() {
+    assert_eq!(
+        run("match (5) { 0 => 1, [] => 2 }"),
+        Err(EvaluationError::NoMatchingArm {
+            value: Object::Integer(5)
+        })
+    );
+    assert_eq!(
+        run("match (5) { x if x => 1 }"),
+        Err(EvaluationError::UnexpectedType {
+            value: Object::Integer(5),
+            expected: "bool"
+        })
+    );
+    assert_eq!(
+        EvaluationError::NoMatchingArm {
+            value: Object::Integer(5)
+        }
+        .to_string(),
+        "no arm of `match` matches integer `5`"
+    );
+}
+
+#[test]
+fn test_default_and_rest_parameters() {
+    let program = "
+        let f = fn(a, b = a * 2, ...rest) { [a, b, rest] };
+        [f(1), f(1, 5), f(1, 5, 6, 7)]
+    ";
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(vec![
+            Object::Array(vec![
+                Object::Integer(1),
+                Object::Integer(2),
+                Object::Array(vec![])
+            ]),
+            Object::Array(vec![
+                Object::Integer(1),
+                Object::Integer(5),
+                Object::Array(vec![])
+            ]),
+            Object::Array(vec![
+                Object::Integer(1),
+                Object::Integer(5),
+                Object::Array(vec![Object::Integer(6), Object::Integer(7)])
+            ]),
+        ]))
+    );
+}
+
+#[test]
+fn test_keyword_arguments() {
+    let program = "
+        let greet = fn(name, greeting = \"hello\", punctuation = \"!\") {
+            greeting + \" \" + name + punctuation
+        };
+        [
+            greet(\"Ana\"),
+            greet(\"Ana\", punctuation: \"?\"),
+            greet(punctuation: \".\", name: \"Bo\", greeting: \"bye\")
+        ]
+    ";
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(vec![
+            Object::Str(String::from("hello Ana!")),
+            Object::Str(String::from("hello Ana?")),
+            Object::Str(String::from("bye Bo.")),
+        ]))
+    );
+}
+
+#[test]
+fn test_keyword_argument_errors() {
+    assert_eq!(
+        run("let f = fn(a) { a }; f(1, b: 2)"),
+        Err(EvaluationError::UnknownKeywordArgument {
+            name: String::from("b")
+        })
+    );
+    assert_eq!(
+        run("let f = fn(a, ...rest) { a }; f(1, rest: 2)"),
+        Err(EvaluationError::UnknownKeywordArgument {
+            name: String::from("rest")
+        })
+    );
+    assert_eq!(
+        run("len(x: [])"),
+        Err(EvaluationError::UnknownKeywordArgument {
+            name: String::from("x")
+        })
+    );
+    assert_eq!(
+        run("let f = fn(a, b) { a }; f(1, a: 2)"),
+        Err(EvaluationError::DuplicateArgument {
+            name: String::from("a")
+        })
+    );
+    assert_eq!(
+        run("let f = fn(a) { a }; f(a: 1, a: 2)"),
+        Err(EvaluationError::DuplicateArgument {
+            name: String::from("a")
+        })
+    );
+}
+
+#[test]
+fn test_wrong_argument_count() {
+    assert_eq!(
+        run("let f = fn(a, b) { a }; f(1)"),
+        Err(EvaluationError::WrongArgumentCount {
+            expected: Arity::exactly(2),
+            got: 1,
+            function: Some(String::from("f"))
+        })
+    );
+    assert_eq!(
+        run("fn(a) { a }(1, 2)"),
+        Err(EvaluationError::WrongArgumentCount {
+            expected: Arity::exactly(1),
+            got: 2,
+            function: None
+        })
+    );
+    assert_eq!(
+        run("let f = fn(a, b = 2, c = 3) { a }; f()"),
+        Err(EvaluationError::WrongArgumentCount {
+            expected: Arity {
+                min: 1,
+                max: Some(3)
+            },
+            got: 0,
+            function: Some(String::from("f"))
+        })
+    );
+    assert_eq!(
+        run("let f = fn(a, ...rest) { a }; f()"),
+        Err(EvaluationError::WrongArgumentCount {
+            expected: Arity { min: 1, max: None },
+            got: 0,
+            function: Some(String::from("f"))
+        })
+    );
+    assert_eq!(
+        run("let f = fn(a, b) { [a, b] }; f(1, b: 2)"),
+        Ok(Object::Array(vec![Object::Integer(1), Object::Integer(2)]))
+    );
+}
+
+#[test]
+fn test_builtin_argument_count() {
+    assert_eq!(
+        run("len([1], [2])"),
+        Err(EvaluationError::WrongArgumentCount {
+            expected: Arity::exactly(1),
+            got: 2,
+            function: Some(String::from("len"))
+        })
+    );
+    assert_eq!(
+        run("let append = push; append([])"),
+        Err(EvaluationError::WrongArgumentCount {
+            expected: Arity::exactly(2),
+            got: 1,
+            function: Some(String::from("push"))
+        })
+    );
+    assert_eq!(
+        run("range(1, 2, 3)"),
+        Err(EvaluationError::WrongArgumentCount {
+            expected: Arity {
+                min: 1,
+                max: Some(2)
+            },
+            got: 3,
+            function: Some(String::from("range"))
+        })
+    );
+    assert_eq!(run("puts()"), Ok(Object::Null));
+}
+
+#[test]
+fn test_wrong_argument_count_display() {
+    let error = |expected, got, function: Option<&str>| EvaluationError::WrongArgumentCount {
+        expected,
+        got,
+        function: function.map(String::from),
+    };
+    assert_eq!(
+        error(Arity::exactly(1), 2, Some("len")).to_string(),
+        "function `len` takes 1 argument, but 2 were given"
+    );
+    let between = Arity {
+        min: 1,
+        max: Some(2),
+    };
+    assert_eq!(
+        error(between, 0, None).to_string(),
+        "function takes 1 to 2 arguments, but 0 were given"
+    );
+    assert_eq!(
+        error(Arity { min: 2, max: None }, 1, Some("f")).to_string(),
+        "function `f` takes at least 2 arguments, but 1 was given"
+    );
+}
+
+#[test]
+fn test_call_argument_errors() {
+    assert_eq!(
+        run("let f = fn(a) { a }; f(1 / 0, 2)"),
+        Err(EvaluationError::DivisionByZero {
+            operation: InfixOperation::Division
+        })
+    );
+    assert_eq!(
+        run("let f = fn(a, b) { b }; f(1, [1][5])"),
+        Err(EvaluationError::IndexOutOfBounds {
+            value: Object::Array(vec![Object::Integer(1)]),
+            index: 5
+        })
+    );
+    assert_eq!(
+        run("let f = fn(a) { a }; let g = fn(a, b) { a }; g(f(f(1 % 0)), 2)"),
+        Err(EvaluationError::DivisionByZero {
+            operation: InfixOperation::Modulo
+        })
+    );
+    assert_eq!(
+        run("len(first([]))"),
+        Err(EvaluationError::IndexOutOfBounds {
+            value: Object::Array(vec![]),
+            index: 0
+        })
+    );
+    assert_eq!(
+        run("let f = fn(a, b = 1) { a }; f(1, b: true + 1)"),
+        Err(EvaluationError::InfixOperationNotImplemented {
+            operation: InfixOperation::Sum,
+            left: Object::Bool(true),
+            right: Object::Integer(1)
+        })
+    );
+}
+
+#[test]
+fn test_call_argument_error_location() {
+    let program = "let f = fn(a, b) { a };\nf(2, 1 / 0)";
+
+    let ast = Parser::new(program.chars()).parse_program().unwrap();
+    let span = eval_program(&ast).unwrap_err().span().unwrap();
+
+    assert_eq!((span.line, span.column, span.end - span.start), (2, 6, 5));
+}
+
+#[test]
+fn test_undefined_variable() {
+    assert_eq!(
+        run("let length = fn(xs) { len(xs) }; lenght([1, 2])"),
+        Err(EvaluationError::UndefinedVariable {
+            name: String::from("lenght"),
+            suggestion: Some(String::from("length"))
+        })
+    );
+    assert_eq!(
+        run("let counter = 0; fn() { let total = 1; conter }()"),
+        Err(EvaluationError::UndefinedVariable {
+            name: String::from("conter"),
+            suggestion: Some(String::from("counter"))
+        })
+    );
+    assert_eq!(
+        run("let total = 0; totl += 1;"),
+        Err(EvaluationError::UndefinedVariable {
+            name: String::from("totl"),
+            suggestion: Some(String::from("total"))
+        })
+    );
+    assert_eq!(
+        run("let xs = [1]; ys[0] = 2;"),
+        Err(EvaluationError::UndefinedVariable {
+            name: String::from("ys"),
+            suggestion: Some(String::from("xs"))
+        })
+    );
+    assert_eq!(
+        run("let a = 1; something_else"),
+        Err(EvaluationError::UndefinedVariable {
+            name: String::from("something_else"),
+            suggestion: None
+        })
+    );
+}
+
+#[test]
+fn test_undefined_variable_diagnostic() {
+    let program = "let items = [1, 2];\nlen(itmes)";
+
+    let ast = Parser::new(program.chars()).parse_program().unwrap();
+    let rendered = eval_program(&ast).unwrap_err().diagnostic().render(program);
+
+    assert_eq!(
+        rendered,
+        "error: cannot find variable `itmes`
+ --> 2:5
+  |
+2 | len(itmes)
+  |     ^^^^^
+  = note: did you mean `items`?
+"
+    );
+}
+
+#[test]
+fn test_return_from_nested_blocks() {
+    let program = "
+        let sign = fn(x) {
+            if (x < 0) { return -1; };
+            if (x == 0) { return 0; } else { if (x > 100) { return 100; }; };
+            1
+        };
+        [sign(-5), sign(0), sign(7), sign(500)]
+    ";
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(vec![
+            Object::Integer(-1),
+            Object::Integer(0),
+            Object::Integer(1),
+            Object::Integer(100)
+        ]))
+    );
+
+    let program = "
+        let describe = fn(x) {
+            let abs = if (x >= 0) { x } else { return \"negative\"; };
+            abs * 2
+        };
+        [describe(3), describe(-3)]
+    ";
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(vec![
+            Object::Integer(6),
+            Object::Str(String::from("negative"))
+        ]))
+    );
+}
+
+#[test]
+fn test_return_from_loops() {
+    let program = "
+        let index_of = fn(xs, target) {
+            for (i, x in xs) {
+                if (x == target) { return i; };
+            }
+            -1
+        };
+        let first_power = fn(limit) {
+            let n = 1;
+            while (true) {
+                n *= 2;
+                if (n > limit) { return n; };
+            }
+        };
+        [index_of([4, 5, 6], 5), index_of([4, 5, 6], 7), first_power(100)]
+    ";
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(vec![
+            Object::Integer(1),
+            Object::Integer(-1),
+            Object::Integer(128)
+        ]))
+    );
+}
+
+#[test]
+fn test_return_only_leaves_the_innermost_function() {
+    let program = "
+        let outer = fn() {
+            let inner = fn() {
+                if (true) { return 1; };
+                2
+            };
+            inner() + 10
+        };
+        outer()
+    ";
+    assert_eq!(run(program), Ok(Object::Integer(11)));
+}
+
+#[test]
+fn test_return_from_default_value() {
+    let program = "
+        let g = fn(x = if (true) { return 5; }) { 1 };
+        let f = fn() { g(); 99 };
+        [g(), f()]
+    ";
+    assert_eq!(
+        run(program),
+        Ok(Object::Array(vec![Object::Integer(5), Object::Integer(99)]))
+    );
+}
+
+#[test]
+fn test_block_value_is_not_a_return() {
+    let program = "
+        let f = fn() {
+            let x = if (true) { 1 } else { 2 };
+            x + 10
+        };
+        f()
+    ";
+    assert_eq!(run(program), Ok(Object::Integer(11)));
+    assert_eq!(run("fn() { 5; }()"), Ok(Object::Null));
+    assert_eq!(run("return 1; 2"), Ok(Object::Integer(1)));
+}
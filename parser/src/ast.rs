@@ -1,14 +1,64 @@
+use crate::span::Span;
+
 #[derive(PartialEq, Clone, Debug)]
 pub enum Statement {
     LetStatement {
-        identifier: String,
+        binding: Binding,
         expression: Expression,
+        span: Span,
     },
     ReturnStatement {
         expression: Expression,
+        span: Span,
+    },
+    /// `x = expression`, or `x += expression` and the other compound
+    /// assignments, which apply `operation` to the current value of `x`
+    AssignStatement {
+        identifier: String,
+        operation: Option<InfixOperation>,
+        expression: Expression,
+        span: Span,
+    },
+    /// `collection[index] = expression`, where `collection` is a variable or
+    /// another indexing of a variable, like in `matrix[i][j] = 0`
+    IndexAssignStatement {
+        collection: Box<Expression>,
+        index: Box<Expression>,
+        operation: Option<InfixOperation>,
+        expression: Expression,
+        span: Span,
     },
     ExpressionStatement {
         expression: Expression,
+        span: Span,
+    },
+    /// The last expression of a block when it is not followed by `;`. Its
+    /// value is the value of the block, unlike `return` it does not leave the
+    /// enclosing function.
+    BlockValueStatement {
+        expression: Expression,
+        span: Span,
+    },
+    WhileStatement {
+        condition: Expression,
+        body: Vec<Statement>,
+        span: Span,
+    },
+    /// `for (value in iterable) { ... }` or `for (key, value in iterable) { ... }`.
+    /// The key of arrays and strings is the index of the value. Iterating a
+    /// hashmap without a key binds the keys of the entries to `value`.
+    ForStatement {
+        key: Option<String>,
+        value: String,
+        iterable: Expression,
+        body: Vec<Statement>,
+        span: Span,
+    },
+    BreakStatement {
+        span: Span,
+    },
+    ContinueStatement {
+        span: Span,
     },
 }
 
@@ -24,62 +74,253 @@ pub enum InfixOperation {
     GreaterThan,
     LessThanEqual,
     GreaterThanEqual,
+    And,
+    Or,
+    Modulo,
+    Power,
+    BitAnd,
+    BitOr,
+    BitXor,
+    ShiftLeft,
+    ShiftRight,
+    Range,
+    RangeInclusive,
 }
 
 #[derive(PartialEq, Clone, Debug)]
 pub enum PrefixOperation {
     Negative,
     Negate,
+    BitNot,
+}
+
+impl std::fmt::Display for InfixOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InfixOperation::Sum => write!(f, "+"),
+            InfixOperation::Product => write!(f, "*"),
+            InfixOperation::Division => write!(f, "/"),
+            InfixOperation::Subtraction => write!(f, "-"),
+            InfixOperation::Equal => write!(f, "=="),
+            InfixOperation::NotEqual => write!(f, "!="),
+            InfixOperation::LessThan => write!(f, "<"),
+            InfixOperation::GreaterThan => write!(f, ">"),
+            InfixOperation::LessThanEqual => write!(f, "<="),
+            InfixOperation::GreaterThanEqual => write!(f, ">="),
+            InfixOperation::And => write!(f, "&&"),
+            InfixOperation::Or => write!(f, "||"),
+            InfixOperation::Modulo => write!(f, "%"),
+            InfixOperation::Power => write!(f, "**"),
+            InfixOperation::BitAnd => write!(f, "&"),
+            InfixOperation::BitOr => write!(f, "|"),
+            InfixOperation::BitXor => write!(f, "^"),
+            InfixOperation::ShiftLeft => write!(f, "<<"),
+            InfixOperation::ShiftRight => write!(f, ">>"),
+            InfixOperation::Range => write!(f, ".."),
+            InfixOperation::RangeInclusive => write!(f, "..="),
+        }
+    }
+}
+
+impl std::fmt::Display for PrefixOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PrefixOperation::Negative => write!(f, "-"),
+            PrefixOperation::Negate => write!(f, "!"),
+            PrefixOperation::BitNot => write!(f, "~"),
+        }
+    }
+}
+
+/// The names bound by a `let` statement: `let x = ...`, or a pattern that
+/// destructures the value, like `let [a, b, ...rest] = xs` and
+/// `let {name, age} = person`.
+#[derive(PartialEq, Clone, Debug)]
+pub enum Binding {
+    Identifier {
+        name: String,
+    },
+    /// Binds each element of an array to the binding in the same position.
+    /// Without `rest` the array must have exactly as many elements, with it
+    /// `rest` receives the remaining ones as an array.
+    Array {
+        elements: Vec<Binding>,
+        rest: Option<String>,
+    },
+    /// Binds each name to the value of the string key with the same name
+    HashMap {
+        keys: Vec<String>,
+    },
+}
+
+/// The pattern of an arm of a `match` expression
+#[derive(PartialEq, Clone, Debug)]
+pub enum Pattern {
+    /// `_`, matches any value without binding it
+    Wildcard,
+    /// An integer, float, string or boolean literal, or a negated number,
+    /// which matches equal values of the same type
+    Literal { value: Expression },
+    /// Matches any value and binds it to `name`
+    Identifier { name: String },
+    /// Matches an array whose elements match the patterns in the same
+    /// position. Without `rest` the array must have exactly as many elements,
+    /// with it `rest` is bound to the remaining ones.
+    Array {
+        elements: Vec<Pattern>,
+        rest: Option<String>,
+    },
+    /// Matches a hashmap that has all the string keys, with values that match
+    /// their patterns. Other keys are ignored, and `{name}` is short for
+    /// `{name: name}`.
+    HashMap { pairs: Vec<(String, Pattern)> },
+}
+
+/// `pattern if guard => body`, the guard is only evaluated when the pattern
+/// matches, with its names bound
+#[derive(PartialEq, Clone, Debug)]
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expression>,
+    pub body: Expression,
+}
+
+/// A parameter of a function, `b = 10` has a default value that is evaluated
+/// when the call does not give one
+#[derive(PartialEq, Clone, Debug)]
+pub struct Parameter {
+    pub name: String,
+    pub default: Option<Expression>,
+}
+
+/// A piece of an interpolated string: `"a ${b}"` has the literal `a ` and the
+/// expression `b`.
+#[derive(PartialEq, Clone, Debug)]
+pub enum StringPart {
+    Literal { value: String },
+    Expression { expression: Expression },
 }
 
 #[derive(PartialEq, Clone, Debug)]
 pub enum Expression {
     IntegerLiteral {
         value: i64,
+        span: Span,
+    },
+    FloatLiteral {
+        value: f64,
+        span: Span,
     },
     StringLiteral {
         value: String,
+        span: Span,
+    },
+    InterpolatedString {
+        parts: Vec<StringPart>,
+        span: Span,
     },
     Array {
         array: Vec<Expression>,
+        span: Span,
     },
     HashMap {
         pairs: Vec<(Expression, Expression)>,
+        span: Span,
     },
     IdentifierExpression {
         identifier: String,
+        span: Span,
     },
     PrefixExpression {
         operation: PrefixOperation,
         right: Box<Expression>,
+        span: Span,
     },
     InfixExpression {
         operation: InfixOperation,
         left: Box<Expression>,
         right: Box<Expression>,
+        span: Span,
     },
     IfExpression {
         condition: Box<Expression>,
         consequence: Vec<Statement>,
         alternative: Option<Vec<Statement>>,
+        span: Span,
+    },
+    /// `match (value) { pattern => body, ... }` evaluates the body of the
+    /// first arm that matches the value
+    MatchExpression {
+        value: Box<Expression>,
+        arms: Vec<MatchArm>,
+        span: Span,
     },
+    /// `fn(a, b = 10, ...rest) { ... }`, where `rest` receives the positional
+    /// arguments left after the parameters as an array
     FunctionExpression {
-        arguments: Vec<String>,
+        arguments: Vec<Parameter>,
+        rest: Option<String>,
         body: Vec<Statement>,
+        span: Span,
     },
+    /// `f(x, key: value)`, the keyword arguments always go after the
+    /// positional ones
     CallExpression {
         function: Box<Expression>,
         arguments: Vec<Expression>,
+        keyword_arguments: Vec<(String, Expression)>,
+        span: Span,
     },
     Index {
         array: Box<Expression>,
         index: Box<Expression>,
+        span: Span,
     },
     Boolean {
         value: bool,
+        span: Span,
     },
 }
 
+impl Statement {
+    pub fn span(&self) -> Span {
+        match self {
+            Statement::LetStatement { span, .. } => *span,
+            Statement::ReturnStatement { span, .. } => *span,
+            Statement::AssignStatement { span, .. } => *span,
+            Statement::IndexAssignStatement { span, .. } => *span,
+            Statement::ExpressionStatement { span, .. } => *span,
+            Statement::BlockValueStatement { span, .. } => *span,
+            Statement::WhileStatement { span, .. } => *span,
+            Statement::ForStatement { span, .. } => *span,
+            Statement::BreakStatement { span } => *span,
+            Statement::ContinueStatement { span } => *span,
+        }
+    }
+}
+
+impl Expression {
+    pub fn span(&self) -> Span {
+        match self {
+            Expression::IntegerLiteral { span, .. } => *span,
+            Expression::FloatLiteral { span, .. } => *span,
+            Expression::StringLiteral { span, .. } => *span,
+            Expression::InterpolatedString { span, .. } => *span,
+            Expression::Array { span, .. } => *span,
+            Expression::HashMap { span, .. } => *span,
+            Expression::IdentifierExpression { span, .. } => *span,
+            Expression::PrefixExpression { span, .. } => *span,
+            Expression::InfixExpression { span, .. } => *span,
+            Expression::IfExpression { span, .. } => *span,
+            Expression::MatchExpression { span, .. } => *span,
+            Expression::FunctionExpression { span, .. } => *span,
+            Expression::CallExpression { span, .. } => *span,
+            Expression::Index { span, .. } => *span,
+            Expression::Boolean { span, .. } => *span,
+        }
+    }
+}
+
 #[derive(PartialEq, Clone, Debug)]
 pub enum Node {
     Statement(Statement),
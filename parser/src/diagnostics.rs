@@ -0,0 +1,194 @@
+use std::fmt::Write;
+
+use super::{IntegerError, ParseError, Span};
+
+/// Additional information attached to a `Diagnostic`, optionally pointing to
+/// another place in the source.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Note {
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+/// A human readable description of an error, that can be rendered together
+/// with the source code it refers to.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Diagnostic {
+    pub message: String,
+    pub span: Span,
+    pub notes: Vec<Note>,
+}
+
+impl Diagnostic {
+    pub fn new(message: String, span: Span) -> Self {
+        Diagnostic {
+            message,
+            span,
+            notes: Vec::new(),
+        }
+    }
+
+    pub fn with_note(mut self, message: String, span: Option<Span>) -> Self {
+        self.notes.push(Note { message, span });
+        self
+    }
+
+    /// Renders the diagnostic in a rustc-like format:
+    ///
+    /// ```text
+    /// error: expected `,` or `)`, found `;`
+    ///  --> 1:9
+    ///   |
+    /// 1 | foo(a, b;
+    ///   |         ^
+    /// ```
+    pub fn render(&self, source: &str) -> String {
+        let mut out = String::new();
+
+        writeln!(out, "error: {}", self.message).unwrap();
+        render_snippet(&mut out, source, self.span);
+
+        for note in &self.notes {
+            match note.span {
+                Some(span) => {
+                    writeln!(out, "note: {}", note.message).unwrap();
+                    render_snippet(&mut out, source, span);
+                }
+                None => writeln!(out, "  = note: {}", note.message).unwrap(),
+            }
+        }
+
+        out
+    }
+}
+
+fn render_snippet(out: &mut String, source: &str, span: Span) {
+    let line_number = span.line.to_string();
+    let gutter = " ".repeat(line_number.len());
+
+    writeln!(out, "{}--> {}:{}", gutter, span.line, span.column).unwrap();
+
+    let mut line_start = 0;
+    for (index, line) in source.split('\n').enumerate() {
+        if index + 1 == span.line {
+            let line = line.trim_end_matches('\r');
+            let line_end = line_start + line.len();
+            let width = source
+                .get(span.start.min(line_end)..span.end.min(line_end))
+                .map(|s| s.chars().count())
+                .unwrap_or(0)
+                .max(1);
+
+            writeln!(out, "{} |", gutter).unwrap();
+            writeln!(out, "{} | {}", line_number, line).unwrap();
+            writeln!(
+                out,
+                "{} | {}{}",
+                gutter,
+                " ".repeat(span.column.saturating_sub(1)),
+                "^".repeat(width)
+            )
+            .unwrap();
+            return;
+        }
+
+        line_start += line.len() + 1;
+    }
+}
+
+impl ParseError {
+    pub fn diagnostic(&self) -> Diagnostic {
+        let diagnostic = Diagnostic::new(self.to_string(), self.span());
+
+        match self {
+            ParseError::UnclosedDelimiter {
+                delimiter, opened, ..
+            } => diagnostic.with_note(
+                format!("to close `{}` opened here", delimiter),
+                Some(*opened),
+            ),
+            ParseError::InvalidEscape { .. } => diagnostic.with_note(
+                String::from(
+                    "valid escapes are `\\n`, `\\t`, `\\r`, `\\0`, `\\\\`, `\\\"`, `\\$` and `\\u{...}`",
+                ),
+                None,
+            ),
+            _ => diagnostic,
+        }
+    }
+}
+
+impl std::fmt::Display for IntegerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            IntegerError::NoDigits => write!(f, "no digits after the prefix"),
+            IntegerError::InvalidDigit { digit, radix } => {
+                let base = match radix {
+                    2 => "binary",
+                    8 => "octal",
+                    16 => "hexadecimal",
+                    _ => "decimal",
+                };
+                write!(f, "invalid digit `{}` in {} literal", digit, base)
+            }
+            IntegerError::Overflow => write!(f, "value does not fit in a 64-bit integer"),
+        }
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::UnexpectedEnd { .. } => write!(f, "unexpected end of input"),
+            ParseError::UnexpectedToken {
+                token, expecting, ..
+            } => write!(f, "expected {}, found `{}`", expecting, token),
+            ParseError::FailedParsingInteger { string, reason, .. } => {
+                write!(f, "invalid integer literal `{}`: {}", string, reason)
+            }
+            ParseError::FailedParsingFloat { string, .. } => {
+                write!(f, "invalid float literal `{}`", string)
+            }
+            ParseError::MissingSemicolon { .. } => write!(f, "missing `;` after statement"),
+            ParseError::NonIdentifierExpression { .. } => {
+                write!(f, "function parameters must be identifiers")
+            }
+            ParseError::UnclosedDelimiter {
+                token: Some(token),
+                expecting,
+                ..
+            } => write!(f, "expected {}, found `{}`", expecting, token),
+            ParseError::UnclosedDelimiter {
+                token: None,
+                expecting,
+                ..
+            } => write!(f, "expected {}, found end of input", expecting),
+            ParseError::UnexpectedCharacter { character, .. } => {
+                write!(f, "unexpected character `{}`", character)
+            }
+            ParseError::UnterminatedString { .. } => write!(f, "unterminated string literal"),
+            ParseError::UnterminatedComment { .. } => write!(f, "unterminated block comment"),
+            ParseError::InvalidEscape { sequence, .. } => {
+                write!(f, "invalid escape sequence `{}`", sequence)
+            }
+            ParseError::InvalidAssignmentTarget { .. } => {
+                write!(f, "invalid left-hand side of assignment")
+            }
+            ParseError::OutsideOfLoop { keyword, .. } => {
+                write!(f, "`{}` outside of a loop", keyword)
+            }
+            ParseError::PositionalAfterKeywordArgument { .. } => {
+                write!(f, "positional arguments must go before keyword arguments")
+            }
+            ParseError::RequiredAfterDefaultParameter { .. } => write!(
+                f,
+                "parameters without a default value must go before the ones with one"
+            ),
+            ParseError::DuplicateParameter { name, .. } => {
+                write!(f, "duplicate parameter `{}`", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -1,4 +1,8 @@
-use std::iter::Iterator;
+use std::iter::{Iterator, Peekable};
+
+use unicode_xid::UnicodeXID;
+
+use crate::span::Span;
 
 #[cfg(test)]
 mod tests;
@@ -7,19 +11,49 @@ mod tests;
 pub enum Token {
     Identifier { name: String },
     Integer { string: String },
+    Float { string: String },
     StringLiteral { string: String },
+    // An interpolated string is split around its `${...}` expressions, whose
+    // tokens are emitted in between: `"a ${b} c ${d} e"` is lexed as
+    // `InterpolationStart("a ")`, `b`, `InterpolationMiddle(" c ")`, `d` and
+    // `InterpolationEnd(" e")`
+    InterpolationStart { string: String },
+    InterpolationMiddle { string: String },
+    InterpolationEnd { string: String },
     Let,
     If,
     Else,
     Function,
     Assign,
+    PlusAssign,
+    MinusAssign,
+    AsteriskAssign,
+    SlashAssign,
     Return,
+    While,
+    For,
+    In,
+    Break,
+    Continue,
+    Match,
+    FatArrow,
 
     Plus,
     Minus,
     Asterisk,
     Slash,
     Bang,
+    Percent,
+    Power,
+    Ampersand,
+    Pipe,
+    Caret,
+    Tilde,
+    ShiftLeft,
+    ShiftRight,
+    DotDot,
+    DotDotEqual,
+    Ellipsis,
 
     True,
     False,
@@ -31,6 +65,9 @@ pub enum Token {
     LessThanEqual,
     GreaterThanEqual,
 
+    And,
+    Or,
+
     OpenParenthesis,
     CloseParenthesis,
     OpenBrace,
@@ -41,50 +78,247 @@ pub enum Token {
     Comma,
     Semicolon,
     Colon,
+
+    // Only emitted by a lexer created with `Lexer::with_comments`
+    LineComment { text: String },
+    BlockComment { text: String },
+
+    // Errors found by the lexer, they are reported by the parser
+    Illegal { character: char },
+    UnterminatedString { string: String },
+    UnterminatedComment,
+    // Only the first invalid escape of a string is reported, the span of the
+    // token is the one of the escape sequence
+    InvalidEscape { sequence: String },
+}
+
+impl Token {
+    pub fn is_error(&self) -> bool {
+        matches!(
+            self,
+            Token::Illegal { .. }
+                | Token::UnterminatedString { .. }
+                | Token::UnterminatedComment
+                | Token::InvalidEscape { .. }
+        )
+    }
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Identifier { name } => write!(f, "{}", name),
+            Token::Integer { string } => write!(f, "{}", string),
+            Token::Float { string } => write!(f, "{}", string),
+            Token::StringLiteral { string } => write!(f, "\"{}\"", string),
+            Token::InterpolationStart { string } => write!(f, "\"{}${{", string),
+            Token::InterpolationMiddle { string } => write!(f, "}}{}${{", string),
+            Token::InterpolationEnd { string } => write!(f, "}}{}\"", string),
+            Token::Let => write!(f, "let"),
+            Token::If => write!(f, "if"),
+            Token::Else => write!(f, "else"),
+            Token::Function => write!(f, "fn"),
+            Token::Assign => write!(f, "="),
+            Token::PlusAssign => write!(f, "+="),
+            Token::MinusAssign => write!(f, "-="),
+            Token::AsteriskAssign => write!(f, "*="),
+            Token::SlashAssign => write!(f, "/="),
+            Token::Return => write!(f, "return"),
+            Token::While => write!(f, "while"),
+            Token::For => write!(f, "for"),
+            Token::In => write!(f, "in"),
+            Token::Break => write!(f, "break"),
+            Token::Continue => write!(f, "continue"),
+            Token::Match => write!(f, "match"),
+            Token::FatArrow => write!(f, "=>"),
+            Token::Plus => write!(f, "+"),
+            Token::Minus => write!(f, "-"),
+            Token::Asterisk => write!(f, "*"),
+            Token::Slash => write!(f, "/"),
+            Token::Bang => write!(f, "!"),
+            Token::Percent => write!(f, "%"),
+            Token::Power => write!(f, "**"),
+            Token::Ampersand => write!(f, "&"),
+            Token::Pipe => write!(f, "|"),
+            Token::Caret => write!(f, "^"),
+            Token::Tilde => write!(f, "~"),
+            Token::ShiftLeft => write!(f, "<<"),
+            Token::ShiftRight => write!(f, ">>"),
+            Token::DotDot => write!(f, ".."),
+            Token::DotDotEqual => write!(f, "..="),
+            Token::Ellipsis => write!(f, "..."),
+            Token::True => write!(f, "true"),
+            Token::False => write!(f, "false"),
+            Token::Equal => write!(f, "=="),
+            Token::NotEqual => write!(f, "!="),
+            Token::LessThan => write!(f, "<"),
+            Token::GreaterThan => write!(f, ">"),
+            Token::LessThanEqual => write!(f, "<="),
+            Token::GreaterThanEqual => write!(f, ">="),
+            Token::And => write!(f, "&&"),
+            Token::Or => write!(f, "||"),
+            Token::OpenParenthesis => write!(f, "("),
+            Token::CloseParenthesis => write!(f, ")"),
+            Token::OpenBrace => write!(f, "{{"),
+            Token::CloseBrace => write!(f, "}}"),
+            Token::OpenSquare => write!(f, "["),
+            Token::CloseSquare => write!(f, "]"),
+            Token::Comma => write!(f, ","),
+            Token::Semicolon => write!(f, ";"),
+            Token::Colon => write!(f, ":"),
+            Token::LineComment { text } => write!(f, "//{}", text),
+            Token::BlockComment { text } => write!(f, "/*{}*/", text),
+            Token::Illegal { character } => write!(f, "{}", character),
+            Token::UnterminatedString { string } => write!(f, "\"{}", string),
+            Token::UnterminatedComment => write!(f, "/*"),
+            Token::InvalidEscape { sequence, .. } => write!(f, "{}", sequence),
+        }
+    }
 }
 
 fn is_whitespace(c: char) -> bool {
     c.is_whitespace()
 }
 
-fn is_letter(c: char) -> bool {
-    c.is_alphabetic()
+// Identifiers follow the Unicode XID rules (the ones used by Rust), and can
+// also start with `_`: `user_id`, `x2`, `_tmp`, `año`
+fn is_identifier_start(c: char) -> bool {
+    c == '_' || UnicodeXID::is_xid_start(c)
 }
 
+fn is_identifier_continue(c: char) -> bool {
+    UnicodeXID::is_xid_continue(c)
+}
+
+// Words that can not be used as identifiers
+const KEYWORDS: &[(&str, Token)] = &[
+    ("let", Token::Let),
+    ("if", Token::If),
+    ("else", Token::Else),
+    ("fn", Token::Function),
+    ("return", Token::Return),
+    ("while", Token::While),
+    ("for", Token::For),
+    ("in", Token::In),
+    ("break", Token::Break),
+    ("continue", Token::Continue),
+    ("match", Token::Match),
+    ("true", Token::True),
+    ("false", Token::False),
+];
+
 fn is_number(c: char) -> bool {
-    c.is_digit(10)
+    c.is_ascii_digit()
+}
+
+#[derive(Clone, Copy)]
+struct Position {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    fn start() -> Self {
+        Position {
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn advance(&mut self, c: char) {
+        self.offset += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
 }
 
 pub struct Lexer<T: Iterator<Item = char>> {
-    iter: T,
+    iter: Peekable<T>,
     saved_char: Option<char>,
+    keep_comments: bool,
+    // Span to use for the current token instead of the one covering all its characters
+    error_span: Option<Span>,
+    // Position of the next character to be read
+    position: Position,
+    // Position where the last character returned by `next_char` starts
+    char_start: Position,
+    // Number of unclosed `{` inside each of the `${...}` being lexed, the
+    // innermost one last
+    interpolations: Vec<usize>,
 }
 
 impl<T: Iterator<Item = char>> Lexer<T> {
     pub fn new(iter: T) -> Self {
         Lexer {
-            iter,
+            iter: iter.peekable(),
             saved_char: None,
+            keep_comments: false,
+            error_span: None,
+            position: Position::start(),
+            char_start: Position::start(),
+            interpolations: Vec::new(),
         }
     }
 
-    fn next_char(&mut self) -> Option<char> {
-        if let Some(c) = self.saved_char {
-            self.saved_char = None;
-            return Some(c);
+    /// Creates a lexer for code that follows the first `offset` bytes of a
+    /// source, which end just before line `line`, so its spans point into the
+    /// whole source.
+    pub fn starting_at(iter: T, offset: usize, line: usize) -> Self {
+        let start = Position {
+            offset,
+            line,
+            column: 1,
+        };
+        Lexer {
+            position: start,
+            char_start: start,
+            ..Self::new(iter)
         }
-        if let Some(c) = self.iter.next() {
-            return Some(c);
+    }
+
+    /// Creates a lexer that emits comments as `LineComment` and `BlockComment`
+    /// tokens instead of skipping them, for tools that need to keep them.
+    pub fn with_comments(iter: T) -> Self {
+        Lexer {
+            keep_comments: true,
+            ..Self::new(iter)
         }
-        None
     }
 
-    fn save_char_for_next_loop(&mut self, c: char) {
-        if !is_whitespace(c) {
-            self.saved_char = Some(c);
+    fn next_char(&mut self) -> Option<char> {
+        let c = match self.saved_char.take() {
+            Some(c) => c,
+            None => self.iter.next()?,
+        };
+
+        self.char_start = self.position;
+        self.position.advance(c);
+        Some(c)
+    }
+
+    // Returns the character that the next call to `next_char` will return
+    fn peek_char(&mut self) -> Option<char> {
+        match self.saved_char {
+            Some(c) => Some(c),
+            None => self.iter.peek().copied(),
         }
     }
 
+    fn save_char_for_next_loop(&mut self, c: char) {
+        self.saved_char = Some(c);
+        self.position = self.char_start;
+    }
+
+    fn span_from(&self, start: Position) -> Span {
+        Span::new(start.offset, self.position.offset, start.line, start.column)
+    }
+
     fn next_char_skipping_whitespace(&mut self) -> Option<char> {
         if let Some(c) = self.next_char() {
             if is_whitespace(c) {
@@ -99,13 +333,14 @@ impl<T: Iterator<Item = char>> Lexer<T> {
         if let Some(c) = self.next_char() {
             match c {
                 '=' => Some(Token::Equal),
+                '>' => Some(Token::FatArrow),
                 remain => {
                     self.save_char_for_next_loop(remain);
                     Some(Token::Assign)
                 }
             }
         } else {
-            None
+            Some(Token::Assign)
         }
     }
 
@@ -119,133 +354,443 @@ impl<T: Iterator<Item = char>> Lexer<T> {
                 }
             }
         } else {
-            None
+            Some(Token::Bang)
         }
     }
 
-    fn next_token_starting_with_less_than(&mut self) -> Option<Token> {
-        if let Some(c) = self.next_char() {
-            match c {
-                '=' => Some(Token::LessThanEqual),
-                remain => {
-                    self.save_char_for_next_loop(remain);
-                    Some(Token::LessThan)
-                }
+    // Returns `token` if the next character is `c`, or `otherwise` if it is not
+    fn next_token_if_followed_by(&mut self, c: char, token: Token, otherwise: Token) -> Token {
+        if self.peek_char() == Some(c) {
+            self.next_char();
+            return token;
+        }
+        otherwise
+    }
+
+    fn next_token_starting_with_less_than(&mut self) -> Token {
+        match self.peek_char() {
+            Some('<') => self.next_token_if_followed_by('<', Token::ShiftLeft, Token::LessThan),
+            _ => self.next_token_if_followed_by('=', Token::LessThanEqual, Token::LessThan),
+        }
+    }
+
+    fn next_token_starting_with_greater_than(&mut self) -> Token {
+        match self.peek_char() {
+            Some('>') => self.next_token_if_followed_by('>', Token::ShiftRight, Token::GreaterThan),
+            _ => self.next_token_if_followed_by('=', Token::GreaterThanEqual, Token::GreaterThan),
+        }
+    }
+
+    fn next_token_starting_with_asterisk(&mut self) -> Token {
+        match self.peek_char() {
+            Some('*') => self.next_token_if_followed_by('*', Token::Power, Token::Asterisk),
+            _ => self.next_token_if_followed_by('=', Token::AsteriskAssign, Token::Asterisk),
+        }
+    }
+
+    fn next_token_starting_with_dot(&mut self) -> Token {
+        if self.peek_char() != Some('.') {
+            return Token::Illegal { character: '.' };
+        }
+
+        self.next_char();
+        match self.peek_char() {
+            Some('.') => self.next_token_if_followed_by('.', Token::Ellipsis, Token::DotDot),
+            _ => self.next_token_if_followed_by('=', Token::DotDotEqual, Token::DotDot),
+        }
+    }
+
+    fn next_token_starting_with_slash(&mut self) -> Option<Token> {
+        match self.next_char() {
+            Some('/') => Some(self.next_line_comment_token()),
+            Some('*') => Some(self.next_block_comment_token()),
+            Some('=') => Some(Token::SlashAssign),
+            Some(remain) => {
+                self.save_char_for_next_loop(remain);
+                Some(Token::Slash)
             }
-        } else {
-            None
+            None => Some(Token::Slash),
         }
     }
 
-    fn next_token_starting_with_greater_than(&mut self) -> Option<Token> {
-        if let Some(c) = self.next_char() {
-            match c {
-                '=' => Some(Token::GreaterThanEqual),
-                remain => {
-                    self.save_char_for_next_loop(remain);
-                    Some(Token::GreaterThan)
+    fn next_line_comment_token(&mut self) -> Token {
+        let mut text = String::new();
+        while let Some(c) = self.next_char() {
+            if c == '\n' {
+                self.save_char_for_next_loop(c);
+                break;
+            }
+            text.push(c);
+        }
+
+        Token::LineComment { text }
+    }
+
+    // Block comments can be nested, so `/* a /* b */ c */` is a single comment
+    fn next_block_comment_token(&mut self) -> Token {
+        let mut text = String::new();
+        let mut depth = 1;
+        loop {
+            match (self.next_char(), self.next_char()) {
+                (Some('*'), Some('/')) => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                    text.push_str("*/");
+                }
+                (Some('/'), Some('*')) => {
+                    depth += 1;
+                    text.push_str("/*");
+                }
+                (Some(c), Some(next)) => {
+                    text.push(c);
+                    self.save_char_for_next_loop(next);
                 }
+                _ => return Token::UnterminatedComment,
             }
-        } else {
-            None
         }
+
+        Token::BlockComment { text }
+    }
+
+    // Digits can be separated by underscores: `1_000_000`
+    fn next_digits(&mut self, string: &mut String) {
+        while let Some(c) = self.next_char() {
+            if !is_number(c) && c != '_' {
+                self.save_char_for_next_loop(c);
+                break;
+            }
+            string.push(c);
+        }
+    }
+
+    // Reads the digits of a `0x`, `0o` or `0b` literal. Letters are included so
+    // that a literal like `0b102` or `0xFG` is reported as a whole by the parser.
+    fn next_radix_integer_token(&mut self, mut string: String) -> Token {
+        while let Some(c) = self.next_char() {
+            if !c.is_alphanumeric() && c != '_' {
+                self.save_char_for_next_loop(c);
+                break;
+            }
+            string.push(c);
+        }
+
+        Token::Integer { string }
     }
 
+    // A number is a float if it has a fractional part (`3.14`) or an exponent
+    // (`1e-3`). The `.` must be followed by a digit, so `1.` is not a float.
     fn next_number_token(&mut self, first: char) -> Token {
         let mut string = first.to_string();
-        loop {
-            if let Some(c) = self.next_char() {
-                if is_number(c) {
-                    string.push(c);
-                    continue;
-                } else {
-                    self.save_char_for_next_loop(c);
+
+        if first == '0' {
+            if let Some(prefix) = self.peek_char().filter(|c| matches!(c, 'x' | 'o' | 'b')) {
+                self.next_char();
+                string.push(prefix);
+                return self.next_radix_integer_token(string);
+            }
+        }
+
+        let mut is_float = false;
+        self.next_digits(&mut string);
+
+        if self.peek_char() == Some('.') {
+            self.next_char();
+            match self.peek_char() {
+                Some(c) if is_number(c) => {
+                    is_float = true;
+                    string.push('.');
+                    self.next_digits(&mut string);
                 }
+                _ => self.save_char_for_next_loop('.'),
             }
-            break;
         }
 
-        Token::Integer { string }
+        if let Some(e) = self.peek_char().filter(|c| *c == 'e' || *c == 'E') {
+            self.next_char();
+            match self.peek_char() {
+                Some(c) if is_number(c) || c == '+' || c == '-' => {
+                    is_float = true;
+                    string.push(e);
+                    string.push(self.next_char().expect("We just peeked"));
+                    self.next_digits(&mut string);
+                }
+                _ => self.save_char_for_next_loop(e),
+            }
+        }
+
+        if is_float {
+            Token::Float { string }
+        } else {
+            Token::Integer { string }
+        }
     }
 
     fn next_word_token(&mut self, first: char) -> Token {
         let mut string = first.to_string();
+        while let Some(c) = self.next_char() {
+            if !is_identifier_continue(c) {
+                self.save_char_for_next_loop(c);
+                break;
+            }
+            string.push(c);
+        }
+
+        match KEYWORDS.iter().find(|(keyword, _)| *keyword == string) {
+            Some((_, token)) => token.clone(),
+            None => Token::Identifier { name: string },
+        }
+    }
+
+    // Reads the hexadecimal code point of a `\u{...}` escape, after the `u`.
+    // On error, returns the part of the escape sequence that has been read.
+    fn next_unicode_escape(&mut self) -> Result<char, String> {
+        let mut sequence = String::from("\\u");
+
+        match self.next_char() {
+            Some('{') => sequence.push('{'),
+            Some(c) => {
+                self.save_char_for_next_loop(c);
+                return Err(sequence);
+            }
+            None => return Err(sequence),
+        }
+
         loop {
-            if let Some(c) = self.next_char() {
-                if is_letter(c) {
-                    string.push(c);
-                    continue;
-                } else {
+            match self.next_char() {
+                Some('}') => {
+                    sequence.push('}');
+                    break;
+                }
+                Some(c) if c.is_ascii_hexdigit() => sequence.push(c),
+                Some(c) => {
+                    self.save_char_for_next_loop(c);
+                    return Err(sequence);
+                }
+                None => return Err(sequence),
+            }
+        }
+
+        let digits = &sequence[3..sequence.len() - 1];
+        if digits.is_empty() || digits.len() > 6 {
+            return Err(sequence);
+        }
+
+        u32::from_str_radix(digits, 16)
+            .ok()
+            .and_then(char::from_u32)
+            .ok_or(sequence)
+    }
+
+    // Reads an escape sequence, after the `\`. A backslash at the end of a line
+    // joins it with the next one, skipping the leading whitespace, and produces
+    // no character.
+    fn next_escaped_char(&mut self) -> Result<Option<char>, String> {
+        match self.next_char() {
+            Some('n') => Ok(Some('\n')),
+            Some('t') => Ok(Some('\t')),
+            Some('r') => Ok(Some('\r')),
+            Some('0') => Ok(Some('\0')),
+            Some('\\') => Ok(Some('\\')),
+            Some('"') => Ok(Some('"')),
+            Some('$') => Ok(Some('$')),
+            Some('u') => self.next_unicode_escape().map(Some),
+            Some('\n') => {
+                if let Some(c) = self.next_char_skipping_whitespace() {
                     self.save_char_for_next_loop(c);
                 }
+                Ok(None)
+            }
+            Some(c) => Err(format!("\\{}", c)),
+            None => Err(String::from("\\")),
+        }
+    }
+
+    // Reads a string literal up to its closing quote or up to the `${` that
+    // starts an interpolated expression. `after_interpolation` tells whether
+    // the string continues after the `}` of a previous expression.
+    fn next_string_literal_token(&mut self, after_interpolation: bool) -> Token {
+        let mut string = String::new();
+        let mut error = None;
+        let interpolation = loop {
+            match self.next_char() {
+                Some('"') => break false,
+                Some('$') => match self.next_char() {
+                    Some('{') => break true,
+                    remain => {
+                        if let Some(c) = remain {
+                            self.save_char_for_next_loop(c);
+                        }
+                        string.push('$');
+                    }
+                },
+                Some('\\') => {
+                    let start = self.char_start;
+                    match self.next_escaped_char() {
+                        Ok(Some(c)) => string.push(c),
+                        Ok(None) => {}
+                        Err(sequence) => {
+                            if error.is_none() {
+                                error = Some(Token::InvalidEscape { sequence });
+                                self.error_span = Some(self.span_from(start));
+                            }
+                        }
+                    }
+                }
+                Some(c) => string.push(c),
+                None => {
+                    // The error is the whole literal, not an escape inside it
+                    self.error_span = None;
+                    return Token::UnterminatedString { string };
+                }
             }
-            break;
+        };
+
+        if interpolation {
+            self.interpolations.push(0);
+        }
+
+        error.unwrap_or(match (after_interpolation, interpolation) {
+            (false, false) => Token::StringLiteral { string },
+            (false, true) => Token::InterpolationStart { string },
+            (true, true) => Token::InterpolationMiddle { string },
+            (true, false) => Token::InterpolationEnd { string },
+        })
+    }
+
+    fn next_token_starting_with_open_brace(&mut self) -> Token {
+        if let Some(depth) = self.interpolations.last_mut() {
+            *depth += 1;
         }
+        Token::OpenBrace
+    }
 
-        match &string[..] {
-            "let" => Token::Let,
-            "if" => Token::If,
-            "else" => Token::Else,
-            "fn" => Token::Function,
-            "return" => Token::Return,
-            "true" => Token::True,
-            "false" => Token::False,
-            _ => Token::Identifier { name: string },
+    // A `}` that is not closing a `{` of the expression ends the `${...}` and
+    // the string literal continues.
+    fn next_token_starting_with_close_brace(&mut self) -> Token {
+        match self.interpolations.last_mut() {
+            Some(0) => {
+                self.interpolations.pop();
+                self.next_string_literal_token(true)
+            }
+            Some(depth) => {
+                *depth -= 1;
+                Token::CloseBrace
+            }
+            None => Token::CloseBrace,
         }
     }
 
-    fn next_string_literal_token(&mut self) -> Token {
+    // Raw strings have no escape sequences: `r"C:\path"`. To include a quote,
+    // the string can be delimited by any number of `#`: `r#"say "hi""#`.
+    fn next_raw_string_literal_token(&mut self, hashes: usize) -> Token {
         let mut string = String::new();
         loop {
-            if let Some(c) = self.next_char() {
-                if c != '"'{
-                    string.push(c);
-                    continue;
-                } else {
-                    break;
+            match self.next_char() {
+                Some('"') => {
+                    let mut closing = 0;
+                    while closing < hashes {
+                        match self.next_char() {
+                            Some('#') => closing += 1,
+                            Some(c) => {
+                                self.save_char_for_next_loop(c);
+                                break;
+                            }
+                            None => break,
+                        }
+                    }
+
+                    if closing == hashes {
+                        return Token::StringLiteral { string };
+                    }
+
+                    string.push('"');
+                    string.push_str(&"#".repeat(closing));
                 }
+                Some(c) => string.push(c),
+                None => return Token::UnterminatedString { string },
             }
-            break;
-        }
-
-        Token::StringLiteral { string }
-    }
-
-    fn next_token(&mut self) -> Option<Token> {
-        if let Some(c) = self.next_char_skipping_whitespace() {
-            return match c {
-                '+' => Some(Token::Plus),
-                '-' => Some(Token::Minus),
-                '*' => Some(Token::Asterisk),
-                '/' => Some(Token::Slash),
-                ';' => Some(Token::Semicolon),
-                ':' => Some(Token::Colon),
-                '(' => Some(Token::OpenParenthesis),
-                ')' => Some(Token::CloseParenthesis),
-                '{' => Some(Token::OpenBrace),
-                '}' => Some(Token::CloseBrace),
-                '[' => Some(Token::OpenSquare),
-                ']' => Some(Token::CloseSquare),
-                ',' => Some(Token::Comma),
-                '!' => self.next_token_starting_with_bang(),
-                '=' => self.next_token_starting_with_equal(),
-                '<' => self.next_token_starting_with_less_than(),
-                '>' => self.next_token_starting_with_greater_than(),
-                '"' => Some(self.next_string_literal_token()),
-                c if is_letter(c) => Some(self.next_word_token(c)),
-                c if is_number(c) => Some(self.next_number_token(c)),
-                _ => panic!(format!("Unexpected character '{}'", c)),
-            };
         }
+    }
 
-        None
+    fn next_token_starting_with_r(&mut self) -> Token {
+        let mut hashes = 0;
+        loop {
+            match self.next_char() {
+                Some('#') => hashes += 1,
+                Some('"') => return self.next_raw_string_literal_token(hashes),
+                remain => {
+                    if let Some(c) = remain {
+                        self.save_char_for_next_loop(c);
+                    }
+
+                    if hashes == 0 {
+                        return self.next_word_token('r');
+                    }
+                    return Token::Illegal { character: '#' };
+                }
+            }
+        }
+    }
+
+    fn next_token_or_comment(&mut self) -> Option<(Token, Span)> {
+        let c = self.next_char_skipping_whitespace()?;
+        let start = self.char_start;
+
+        let token = match c {
+            '+' => Some(self.next_token_if_followed_by('=', Token::PlusAssign, Token::Plus)),
+            '-' => Some(self.next_token_if_followed_by('=', Token::MinusAssign, Token::Minus)),
+            '*' => Some(self.next_token_starting_with_asterisk()),
+            '%' => Some(Token::Percent),
+            '^' => Some(Token::Caret),
+            '~' => Some(Token::Tilde),
+            '/' => self.next_token_starting_with_slash(),
+            ';' => Some(Token::Semicolon),
+            ':' => Some(Token::Colon),
+            '(' => Some(Token::OpenParenthesis),
+            ')' => Some(Token::CloseParenthesis),
+            '{' => Some(self.next_token_starting_with_open_brace()),
+            '}' => Some(self.next_token_starting_with_close_brace()),
+            '[' => Some(Token::OpenSquare),
+            ']' => Some(Token::CloseSquare),
+            ',' => Some(Token::Comma),
+            '.' => Some(self.next_token_starting_with_dot()),
+            '&' => Some(self.next_token_if_followed_by('&', Token::And, Token::Ampersand)),
+            '|' => Some(self.next_token_if_followed_by('|', Token::Or, Token::Pipe)),
+            '!' => self.next_token_starting_with_bang(),
+            '=' => self.next_token_starting_with_equal(),
+            '<' => Some(self.next_token_starting_with_less_than()),
+            '>' => Some(self.next_token_starting_with_greater_than()),
+            '"' => Some(self.next_string_literal_token(false)),
+            'r' => Some(self.next_token_starting_with_r()),
+            c if is_identifier_start(c) => Some(self.next_word_token(c)),
+            c if is_number(c) => Some(self.next_number_token(c)),
+            c => Some(Token::Illegal { character: c }),
+        };
+
+        let span = self
+            .error_span
+            .take()
+            .unwrap_or_else(|| self.span_from(start));
+        token.map(|t| (t, span))
+    }
+
+    fn next_token(&mut self) -> Option<(Token, Span)> {
+        loop {
+            match self.next_token_or_comment()? {
+                (Token::LineComment { .. }, _) | (Token::BlockComment { .. }, _)
+                    if !self.keep_comments => {}
+                token => return Some(token),
+            }
+        }
     }
 }
 
 impl<T: Iterator<Item = char>> Iterator for Lexer<T> {
-    type Item = Token;
+    type Item = (Token, Span);
 
-    fn next(&mut self) -> Option<Token> {
+    fn next(&mut self) -> Option<(Token, Span)> {
         self.next_token()
     }
 }
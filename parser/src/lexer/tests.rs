@@ -1,7 +1,13 @@
 use super::{Lexer, Token};
 
 fn lex_string(code: &str) -> Vec<Token> {
-    Lexer::new(code.chars()).collect()
+    Lexer::new(code.chars()).map(|(token, _)| token).collect()
+}
+
+fn lex_positions(code: &str) -> Vec<(usize, usize, usize, usize)> {
+    Lexer::new(code.chars())
+        .map(|(_, span)| (span.start, span.end, span.line, span.column))
+        .collect()
 }
 
 #[test]
@@ -47,6 +53,7 @@ fn test_multiple_char_tokens() {
         ! =
            = =
            >= <=
+        => = >
         ";
 
     let expected_tokens = vec![
@@ -58,6 +65,9 @@ fn test_multiple_char_tokens() {
         Token::Assign,
         Token::GreaterThanEqual,
         Token::LessThanEqual,
+        Token::FatArrow,
+        Token::Assign,
+        Token::GreaterThan,
     ];
 
     assert_eq!(lex_string(code), expected_tokens);
@@ -98,7 +108,7 @@ fn test_number_tokens() {
 #[test]
 fn test_word_tokens() {
     let code = "
-            let if else fn return true false
+            let if else fn return true false match
             Animal dog Cat mandarinA
         ";
 
@@ -110,6 +120,7 @@ fn test_word_tokens() {
         Token::Return,
         Token::True,
         Token::False,
+        Token::Match,
         Token::Identifier {
             name: String::from("Animal"),
         },
@@ -196,3 +207,483 @@ fn test_string_literal() {
 
     assert_eq!(lex_string(code), expected_tokens);
 }
+
+#[test]
+fn test_token_spans() {
+    let code = "let ab = 42;\n  b >= \"xé\"";
+
+    let expected_positions = vec![
+        (0, 3, 1, 1),
+        (4, 6, 1, 5),
+        (7, 8, 1, 8),
+        (9, 11, 1, 10),
+        (11, 12, 1, 12),
+        (15, 16, 2, 3),
+        (17, 19, 2, 5),
+        (20, 25, 2, 8),
+    ];
+
+    assert_eq!(lex_positions(code), expected_positions);
+}
+
+#[test]
+fn test_illegal_characters() {
+    let code = "a # b @'";
+
+    let expected_tokens = vec![
+        Token::Identifier {
+            name: String::from("a"),
+        },
+        Token::Illegal { character: '#' },
+        Token::Identifier {
+            name: String::from("b"),
+        },
+        Token::Illegal { character: '@' },
+        Token::Illegal { character: '\'' },
+    ];
+
+    assert_eq!(lex_string(code), expected_tokens);
+}
+
+#[test]
+fn test_unterminated_string() {
+    let code = "let a = \"hello;\n";
+
+    let expected_tokens = vec![
+        Token::Let,
+        Token::Identifier {
+            name: String::from("a"),
+        },
+        Token::Assign,
+        Token::UnterminatedString {
+            string: String::from("hello;\n"),
+        },
+    ];
+
+    assert_eq!(lex_string(code), expected_tokens);
+    assert_eq!(lex_positions(code)[3], (8, 16, 1, 9));
+
+    // An invalid escape does not change the location of the whole literal
+    let code = "\"a\\q";
+    assert_eq!(
+        lex_string(code),
+        vec![Token::UnterminatedString {
+            string: String::from("a"),
+        }]
+    );
+    assert_eq!(lex_positions(code), vec![(0, 4, 1, 1)]);
+}
+
+#[test]
+fn test_comments_are_skipped() {
+    let code = "
+        // a line comment
+        a / b // another one
+        /* a block
+           comment */ c
+        /* nested /* block */ comment */ 1
+        ";
+
+    let expected_tokens = vec![
+        Token::Identifier {
+            name: String::from("a"),
+        },
+        Token::Slash,
+        Token::Identifier {
+            name: String::from("b"),
+        },
+        Token::Identifier {
+            name: String::from("c"),
+        },
+        Token::Integer {
+            string: String::from("1"),
+        },
+    ];
+
+    assert_eq!(lex_string(code), expected_tokens);
+}
+
+#[test]
+fn test_comments_are_preserved() {
+    let code = "a // one\n/* two /* three */ **/ b";
+
+    let tokens: Vec<(Token, (usize, usize, usize, usize))> = Lexer::with_comments(code.chars())
+        .map(|(token, span)| (token, (span.start, span.end, span.line, span.column)))
+        .collect();
+
+    let expected_tokens = vec![
+        (
+            Token::Identifier {
+                name: String::from("a"),
+            },
+            (0, 1, 1, 1),
+        ),
+        (
+            Token::LineComment {
+                text: String::from(" one"),
+            },
+            (2, 8, 1, 3),
+        ),
+        (
+            Token::BlockComment {
+                text: String::from(" two /* three */ *"),
+            },
+            (9, 31, 2, 1),
+        ),
+        (
+            Token::Identifier {
+                name: String::from("b"),
+            },
+            (32, 33, 2, 24),
+        ),
+    ];
+
+    assert_eq!(tokens, expected_tokens);
+}
+
+#[test]
+fn test_unterminated_comment() {
+    let code = "a /* b /* c */";
+
+    let expected_tokens = vec![
+        Token::Identifier {
+            name: String::from("a"),
+        },
+        Token::UnterminatedComment,
+    ];
+
+    assert_eq!(lex_string(code), expected_tokens);
+}
+
+#[test]
+fn test_string_escapes() {
+    let code = r#"
+        "a\"b\\c\nd\te\u{e9}\u{1F600}"
+        "one \
+         two"
+        "multi
+line"
+        "#;
+
+    let expected_tokens = vec![
+        Token::StringLiteral {
+            string: String::from("a\"b\\c\nd\te\u{e9}\u{1F600}"),
+        },
+        Token::StringLiteral {
+            string: String::from("one two"),
+        },
+        Token::StringLiteral {
+            string: String::from("multi\nline"),
+        },
+    ];
+
+    assert_eq!(lex_string(code), expected_tokens);
+}
+
+#[test]
+fn test_invalid_escapes() {
+    let codes = [
+        r#""a\qb""#,
+        r#""\u{110000}""#,
+        r#""\u{}""#,
+        r#""\u{12x}""#,
+        r#""\u12""#,
+    ];
+
+    let sequences = codes
+        .iter()
+        .map(|code| match &lex_string(code)[..] {
+            [Token::InvalidEscape { sequence, .. }] => sequence.clone(),
+            tokens => panic!("Unexpected tokens {:?}", tokens),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        sequences,
+        vec![r"\q", r"\u{110000}", r"\u{}", r"\u{12", r"\u"]
+    );
+}
+
+#[test]
+fn test_raw_strings() {
+    let code = r###"
+        r"C:\path" r#"say "hi""# r##"a "# b"## rest r
+        "###;
+
+    let expected_tokens = vec![
+        Token::StringLiteral {
+            string: String::from(r"C:\path"),
+        },
+        Token::StringLiteral {
+            string: String::from(r#"say "hi""#),
+        },
+        Token::StringLiteral {
+            string: String::from(r##"a "# b"##),
+        },
+        Token::Identifier {
+            name: String::from("rest"),
+        },
+        Token::Identifier {
+            name: String::from("r"),
+        },
+    ];
+
+    assert_eq!(lex_string(code), expected_tokens);
+}
+
+#[test]
+fn test_string_interpolation() {
+    let code = r#""a ${b} c ${ {"d": 1}["d"] }" "${"${x}"}$" "\${y}""#;
+
+    let expected_tokens = vec![
+        Token::InterpolationStart {
+            string: String::from("a "),
+        },
+        Token::Identifier {
+            name: String::from("b"),
+        },
+        Token::InterpolationMiddle {
+            string: String::from(" c "),
+        },
+        Token::OpenBrace,
+        Token::StringLiteral {
+            string: String::from("d"),
+        },
+        Token::Colon,
+        Token::Integer {
+            string: String::from("1"),
+        },
+        Token::CloseBrace,
+        Token::OpenSquare,
+        Token::StringLiteral {
+            string: String::from("d"),
+        },
+        Token::CloseSquare,
+        Token::InterpolationEnd {
+            string: String::from(""),
+        },
+        Token::InterpolationStart {
+            string: String::from(""),
+        },
+        Token::InterpolationStart {
+            string: String::from(""),
+        },
+        Token::Identifier {
+            name: String::from("x"),
+        },
+        Token::InterpolationEnd {
+            string: String::from(""),
+        },
+        Token::InterpolationEnd {
+            string: String::from("$"),
+        },
+        Token::StringLiteral {
+            string: String::from("${y}"),
+        },
+    ];
+
+    assert_eq!(lex_string(code), expected_tokens);
+}
+
+#[test]
+fn test_float_tokens() {
+    let code = "3.14 1e-3 2.5E+10 7e2 1. 1.x 2elf";
+
+    let expected_tokens = vec![
+        Token::Float {
+            string: String::from("3.14"),
+        },
+        Token::Float {
+            string: String::from("1e-3"),
+        },
+        Token::Float {
+            string: String::from("2.5E+10"),
+        },
+        Token::Float {
+            string: String::from("7e2"),
+        },
+        Token::Integer {
+            string: String::from("1"),
+        },
+        Token::Illegal { character: '.' },
+        Token::Integer {
+            string: String::from("1"),
+        },
+        Token::Illegal { character: '.' },
+        Token::Identifier {
+            name: String::from("x"),
+        },
+        Token::Integer {
+            string: String::from("2"),
+        },
+        Token::Identifier {
+            name: String::from("elf"),
+        },
+    ];
+
+    assert_eq!(lex_string(code), expected_tokens);
+    assert_eq!(lex_positions("1.5 x")[1], (4, 5, 1, 5));
+}
+
+#[test]
+fn test_radix_and_underscore_number_tokens() {
+    let code = "0xFF 0b1010 0o755 1_000_000 0b102 0x 1_0.5";
+
+    let expected_tokens = ["0xFF", "0b1010", "0o755", "1_000_000", "0b102", "0x"]
+        .iter()
+        .map(|s| Token::Integer {
+            string: String::from(*s),
+        })
+        .chain(std::iter::once(Token::Float {
+            string: String::from("1_0.5"),
+        }))
+        .collect::<Vec<_>>();
+
+    assert_eq!(lex_string(code), expected_tokens);
+}
+
+#[test]
+fn test_identifier_tokens() {
+    let code = "user_id x2 _tmp _ año δx let_ lets 2x";
+
+    let expected_tokens = vec![
+        Token::Identifier {
+            name: String::from("user_id"),
+        },
+        Token::Identifier {
+            name: String::from("x2"),
+        },
+        Token::Identifier {
+            name: String::from("_tmp"),
+        },
+        Token::Identifier {
+            name: String::from("_"),
+        },
+        Token::Identifier {
+            name: String::from("año"),
+        },
+        Token::Identifier {
+            name: String::from("δx"),
+        },
+        Token::Identifier {
+            name: String::from("let_"),
+        },
+        Token::Identifier {
+            name: String::from("lets"),
+        },
+        Token::Integer {
+            string: String::from("2"),
+        },
+        Token::Identifier {
+            name: String::from("x"),
+        },
+    ];
+
+    assert_eq!(lex_string(code), expected_tokens);
+}
+
+#[test]
+fn test_logical_operator_tokens() {
+    let code = "&& || & | &&& |||";
+
+    let expected_tokens = vec![
+        Token::And,
+        Token::Or,
+        Token::Ampersand,
+        Token::Pipe,
+        Token::And,
+        Token::Ampersand,
+        Token::Or,
+        Token::Pipe,
+    ];
+
+    assert_eq!(lex_string(code), expected_tokens);
+}
+
+#[test]
+fn test_arithmetic_and_bitwise_operator_tokens() {
+    let code = "% ** * ^ ~ << <= < >> >= > <";
+
+    let expected_tokens = vec![
+        Token::Percent,
+        Token::Power,
+        Token::Asterisk,
+        Token::Caret,
+        Token::Tilde,
+        Token::ShiftLeft,
+        Token::LessThanEqual,
+        Token::LessThan,
+        Token::ShiftRight,
+        Token::GreaterThanEqual,
+        Token::GreaterThan,
+        Token::LessThan,
+    ];
+
+    assert_eq!(lex_string(code), expected_tokens);
+}
+
+#[test]
+fn test_range_tokens() {
+    let code = "0..n 1..=2 a . b ...rest";
+
+    let expected_tokens = vec![
+        Token::Integer {
+            string: String::from("0"),
+        },
+        Token::DotDot,
+        Token::Identifier {
+            name: String::from("n"),
+        },
+        Token::Integer {
+            string: String::from("1"),
+        },
+        Token::DotDotEqual,
+        Token::Integer {
+            string: String::from("2"),
+        },
+        Token::Identifier {
+            name: String::from("a"),
+        },
+        Token::Illegal { character: '.' },
+        Token::Identifier {
+            name: String::from("b"),
+        },
+        Token::Ellipsis,
+        Token::Identifier {
+            name: String::from("rest"),
+        },
+    ];
+
+    assert_eq!(lex_string(code), expected_tokens);
+}
+
+#[test]
+fn test_assignment_operator_tokens() {
+    let code = "= += -= *= /= ** - /";
+
+    let expected_tokens = vec![
+        Token::Assign,
+        Token::PlusAssign,
+        Token::MinusAssign,
+        Token::AsteriskAssign,
+        Token::SlashAssign,
+        Token::Power,
+        Token::Minus,
+        Token::Slash,
+    ];
+
+    assert_eq!(lex_string(code), expected_tokens);
+}
+
+#[test]
+fn test_operator_at_end_of_input() {
+    let expected_tokens = vec![
+        Token::Identifier {
+            name: String::from("x"),
+        },
+        Token::Assign,
+    ];
+    assert_eq!(lex_string("x ="), expected_tokens);
+    assert_eq!(lex_string("!"), vec![Token::Bang]);
+    assert_eq!(lex_string("/"), vec![Token::Slash]);
+}
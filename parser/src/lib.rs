@@ -1,16 +1,28 @@
 pub mod ast;
-mod lexer;
+pub mod diagnostics;
+pub mod lexer;
+mod span;
 
 use lexer::Token;
+pub use span::Span;
 
 #[derive(PartialOrd, PartialEq)]
 enum Precedence {
     Lowest,
+    Range,
+    Or,
+    And,
     Equal,
     LessGreater,
+    BitOr,
+    BitXor,
+    BitAnd,
+    Shift,
     Sum,
     Product,
     Prefix,
+    // Higher than prefix operators so that `-2 ** 2` is `-(2 ** 2)`
+    Power,
 }
 
 fn infix_operator_precedence(operation: &ast::InfixOperation) -> Precedence {
@@ -25,21 +37,166 @@ fn infix_operator_precedence(operation: &ast::InfixOperation) -> Precedence {
         ast::InfixOperation::GreaterThan => Precedence::LessGreater,
         ast::InfixOperation::LessThanEqual => Precedence::LessGreater,
         ast::InfixOperation::GreaterThanEqual => Precedence::LessGreater,
+        ast::InfixOperation::And => Precedence::And,
+        ast::InfixOperation::Or => Precedence::Or,
+        ast::InfixOperation::Modulo => Precedence::Product,
+        ast::InfixOperation::Power => Precedence::Power,
+        ast::InfixOperation::BitAnd => Precedence::BitAnd,
+        ast::InfixOperation::BitOr => Precedence::BitOr,
+        ast::InfixOperation::BitXor => Precedence::BitXor,
+        ast::InfixOperation::ShiftLeft => Precedence::Shift,
+        ast::InfixOperation::ShiftRight => Precedence::Shift,
+        ast::InfixOperation::Range => Precedence::Range,
+        ast::InfixOperation::RangeInclusive => Precedence::Range,
+    }
+}
+
+/// Why an integer literal could not be parsed.
+#[derive(PartialEq, Clone, Copy, Debug)]
+pub enum IntegerError {
+    /// A radix prefix without digits after it, like `0x`
+    NoDigits,
+    InvalidDigit {
+        digit: char,
+        radix: u32,
+    },
+    /// The value does not fit in an `i64`
+    Overflow,
+}
+
+// Parses a decimal, `0x` hexadecimal, `0o` octal or `0b` binary literal, whose
+// digits can be separated by underscores.
+fn parse_integer(string: &str) -> Result<i64, IntegerError> {
+    let (radix, digits) = match string.get(..2) {
+        Some("0x") => (16, &string[2..]),
+        Some("0o") => (8, &string[2..]),
+        Some("0b") => (2, &string[2..]),
+        _ => (10, string),
+    };
+
+    let digits: String = digits.chars().filter(|c| *c != '_').collect();
+    if digits.is_empty() {
+        return Err(IntegerError::NoDigits);
+    }
+
+    if let Some(digit) = digits.chars().find(|c| !c.is_digit(radix)) {
+        return Err(IntegerError::InvalidDigit { digit, radix });
+    }
+
+    i64::from_str_radix(&digits, radix).map_err(|_| IntegerError::Overflow)
+}
+
+// Variables, and indexings of variables like `a[1]["b"]`, can be assigned to
+fn is_assignable(expression: &ast::Expression) -> bool {
+    match expression {
+        ast::Expression::IdentifierExpression { .. } => true,
+        ast::Expression::Index { array, .. } => is_assignable(array),
+        _ => false,
     }
 }
 
 #[derive(PartialEq, Debug)]
 pub enum ParseError {
-    UnexpectedEnd,
-    UnexpectedToken { token: Token, expecting: String },
-    FailedParsingInteger { string: String },
-    MissingSemicolon,
-    NonIdentifierExpression,
+    UnexpectedEnd {
+        span: Span,
+    },
+    UnexpectedToken {
+        token: Token,
+        expecting: String,
+        span: Span,
+    },
+    FailedParsingInteger {
+        string: String,
+        reason: IntegerError,
+        span: Span,
+    },
+    FailedParsingFloat {
+        string: String,
+        span: Span,
+    },
+    MissingSemicolon {
+        span: Span,
+    },
+    NonIdentifierExpression {
+        span: Span,
+    },
+    UnclosedDelimiter {
+        delimiter: char,
+        token: Option<Token>,
+        expecting: String,
+        opened: Span,
+        span: Span,
+    },
+    UnexpectedCharacter {
+        character: char,
+        span: Span,
+    },
+    UnterminatedString {
+        span: Span,
+    },
+    UnterminatedComment {
+        span: Span,
+    },
+    InvalidEscape {
+        sequence: String,
+        span: Span,
+    },
+    // The left side of an assignment is not a variable or an indexing of one
+    InvalidAssignmentTarget {
+        span: Span,
+    },
+    // A `break` or `continue` that is not inside a loop
+    OutsideOfLoop {
+        keyword: Token,
+        span: Span,
+    },
+    PositionalAfterKeywordArgument {
+        span: Span,
+    },
+    // A parameter without a default value after one that has it
+    RequiredAfterDefaultParameter {
+        span: Span,
+    },
+    DuplicateParameter {
+        name: String,
+        span: Span,
+    },
+}
+
+impl ParseError {
+    pub fn span(&self) -> Span {
+        match self {
+            ParseError::UnexpectedEnd { span } => *span,
+            ParseError::UnexpectedToken { span, .. } => *span,
+            ParseError::FailedParsingInteger { span, .. } => *span,
+            ParseError::FailedParsingFloat { span, .. } => *span,
+            ParseError::MissingSemicolon { span } => *span,
+            ParseError::NonIdentifierExpression { span } => *span,
+            ParseError::UnclosedDelimiter { span, .. } => *span,
+            ParseError::UnexpectedCharacter { span, .. } => *span,
+            ParseError::UnterminatedString { span } => *span,
+            ParseError::UnterminatedComment { span } => *span,
+            ParseError::InvalidEscape { span, .. } => *span,
+            ParseError::InvalidAssignmentTarget { span } => *span,
+            ParseError::OutsideOfLoop { span, .. } => *span,
+            ParseError::PositionalAfterKeywordArgument { span } => *span,
+            ParseError::RequiredAfterDefaultParameter { span } => *span,
+            ParseError::DuplicateParameter { span, .. } => *span,
+        }
+    }
 }
 
 pub struct Parser<T: Iterator<Item = char>> {
     lexer: lexer::Lexer<T>,
-    token_buffer: Vec<Token>,
+    token_buffer: Vec<(Token, Span)>,
+    // Span of the last token returned by `next_token`, and of the one before it,
+    // so `save_token` can go back
+    last_span: Span,
+    previous_span: Span,
+    // Number of braces opened and not yet closed by the consumed tokens
+    depth: isize,
+    // Number of loops around the current statement, inside the current function
+    loop_depth: usize,
 }
 
 impl<T: Iterator<Item = char>> Parser<T> {
@@ -47,18 +204,63 @@ impl<T: Iterator<Item = char>> Parser<T> {
         Parser {
             lexer: lexer::Lexer::new(iter),
             token_buffer: Vec::new(),
+            last_span: Span::default(),
+            previous_span: Span::default(),
+            depth: 0,
+            loop_depth: 0,
+        }
+    }
+
+    /// Creates a parser for code that follows the first `offset` bytes of a
+    /// source, which end just before line `line`. See `Lexer::starting_at`.
+    pub fn starting_at(iter: T, offset: usize, line: usize) -> Self {
+        Parser {
+            lexer: lexer::Lexer::starting_at(iter, offset, line),
+            token_buffer: Vec::new(),
+            last_span: Span::default(),
+            previous_span: Span::default(),
+            depth: 0,
+            loop_depth: 0,
         }
     }
 
     fn next_token(&mut self) -> Option<Token> {
-        match self.token_buffer.pop() {
-            Some(t) => Some(t),
-            None => self.lexer.next(),
+        let (token, span) = match self.token_buffer.pop() {
+            Some(t) => t,
+            None => self.lexer.next()?,
+        };
+
+        match token {
+            Token::OpenBrace => self.depth += 1,
+            Token::CloseBrace => self.depth -= 1,
+            _ => {}
         }
+
+        self.previous_span = self.last_span;
+        self.last_span = span;
+        Some(token)
     }
 
     fn save_token(&mut self, t: Token) {
-        self.token_buffer.push(t);
+        match t {
+            Token::OpenBrace => self.depth -= 1,
+            Token::CloseBrace => self.depth += 1,
+            _ => {}
+        }
+
+        self.token_buffer.push((t, self.last_span));
+        self.last_span = self.previous_span;
+    }
+
+    /// Span that goes from `start` to the end of the last consumed token.
+    fn span_from(&self, start: Span) -> Span {
+        start.to(self.last_span)
+    }
+
+    fn unexpected_end(&self) -> ParseError {
+        ParseError::UnexpectedEnd {
+            span: self.last_span,
+        }
     }
 
     fn peek_next_token(&mut self) -> Option<&Token> {
@@ -69,13 +271,68 @@ impl<T: Iterator<Item = char>> Parser<T> {
             }
         }
 
-        Some(&self.token_buffer[0])
+        Some(&self.token_buffer[0].0)
+    }
+
+    /// The lexer does not fail, it emits error tokens instead. No rule of the
+    /// grammar accepts them, so they are turned into errors when the parser
+    /// finds one where it expected something else.
+    fn lexer_error(&self, token: &Token) -> Option<ParseError> {
+        match token {
+            Token::Illegal { character } => Some(ParseError::UnexpectedCharacter {
+                character: *character,
+                span: self.last_span,
+            }),
+            Token::UnterminatedString { .. } => Some(ParseError::UnterminatedString {
+                span: self.last_span,
+            }),
+            Token::UnterminatedComment => Some(ParseError::UnterminatedComment {
+                span: self.last_span,
+            }),
+            Token::InvalidEscape { sequence } => Some(ParseError::InvalidEscape {
+                sequence: sequence.clone(),
+                span: self.last_span,
+            }),
+            _ => None,
+        }
+    }
+
+    fn unexpected_token(&self, token: Token, expecting: &str) -> ParseError {
+        if let Some(e) = self.lexer_error(&token) {
+            return e;
+        }
+
+        ParseError::UnexpectedToken {
+            token,
+            expecting: String::from(expecting),
+            span: self.last_span,
+        }
+    }
+
+    fn unclosed_delimiter(
+        &self,
+        delimiter: char,
+        opened: Span,
+        token: Option<Token>,
+        expecting: &str,
+    ) -> ParseError {
+        if let Some(e) = token.as_ref().and_then(|t| self.lexer_error(t)) {
+            return e;
+        }
+
+        ParseError::UnclosedDelimiter {
+            delimiter,
+            token,
+            expecting: String::from(expecting),
+            opened,
+            span: self.last_span,
+        }
     }
 
     fn skip_token(&mut self) -> Result<(), ParseError> {
         match self.next_token() {
             Some(_) => Ok(()),
-            None => Err(ParseError::UnexpectedEnd),
+            None => Err(self.unexpected_end()),
         }
     }
 
@@ -85,12 +342,23 @@ impl<T: Iterator<Item = char>> Parser<T> {
                 return Ok(());
             }
 
-            return Err(ParseError::UnexpectedToken {
-                token: t,
-                expecting: format!("{:?}", compare_to),
-            });
+            return Err(self.unexpected_token(t, &format!("`{}`", compare_to)));
+        }
+        Err(self.unexpected_end())
+    }
+
+    fn skip_closing_delimiter(&mut self, closing: Token, opened: Span) -> Result<(), ParseError> {
+        let delimiter = match closing {
+            Token::CloseParenthesis => '(',
+            Token::CloseSquare => '[',
+            Token::CloseBrace => '{',
+            _ => unreachable!("{} is not a closing delimiter", closing),
+        };
+
+        match self.next_token() {
+            Some(t) if t == closing => Ok(()),
+            t => Err(self.unclosed_delimiter(delimiter, opened, t, &format!("`{}`", closing))),
         }
-        Err(ParseError::UnexpectedEnd)
     }
 
     fn parse_identifier(&mut self) -> Result<String, ParseError> {
@@ -99,39 +367,238 @@ impl<T: Iterator<Item = char>> Parser<T> {
             if let Token::Identifier { name, .. } = identifier_token {
                 Ok(name)
             } else {
-                Err(ParseError::UnexpectedToken {
-                    token: identifier_token,
-                    expecting: String::from("Identifier"),
-                })
+                Err(self.unexpected_token(identifier_token, "identifier"))
             }
         } else {
-            Err(ParseError::UnexpectedEnd)
+            Err(self.unexpected_end())
         }
     }
 
     fn parse_let_statement(&mut self) -> Result<ast::Statement, ParseError> {
-        let identifier = self.parse_identifier()?;
+        let start = self.last_span;
+        let binding = self.parse_binding()?;
         self.skip_token_expecting(Token::Assign)?;
         let expression = self.parse_expression(Precedence::Lowest)?;
         Ok(ast::Statement::LetStatement {
-            identifier,
+            binding,
             expression,
+            span: self.span_from(start),
         })
     }
 
+    fn parse_binding(&mut self) -> Result<ast::Binding, ParseError> {
+        match self.next_token() {
+            Some(Token::Identifier { name }) => Ok(ast::Binding::Identifier { name }),
+            Some(Token::OpenSquare) => self.parse_array_binding(),
+            Some(Token::OpenBrace) => self.parse_hashmap_binding(),
+            Some(t) => Err(self.unexpected_token(t, "identifier, `[` or `{`")),
+            None => Err(self.unexpected_end()),
+        }
+    }
+
+    // Parses `[a, [b, c], ...rest]` after the `[`
+    fn parse_array_binding(&mut self) -> Result<ast::Binding, ParseError> {
+        let start = self.last_span;
+        let mut elements = Vec::new();
+        let mut rest = None;
+
+        if let Some(Token::CloseSquare) = self.peek_next_token() {
+            self.skip_token().expect("We just peeked");
+            return Ok(ast::Binding::Array { elements, rest });
+        };
+
+        loop {
+            if let Some(Token::Ellipsis) = self.peek_next_token() {
+                self.skip_token().expect("We just peeked");
+                rest = Some(self.parse_identifier()?);
+                self.skip_closing_delimiter(Token::CloseSquare, start)?;
+                break;
+            }
+
+            elements.push(self.parse_binding()?);
+
+            match self.next_token() {
+                Some(Token::Comma) => continue,
+                Some(Token::CloseSquare) => break,
+                t => return Err(self.unclosed_delimiter('[', start, t, "`,` or `]`")),
+            };
+        }
+
+        Ok(ast::Binding::Array { elements, rest })
+    }
+
+    // Parses `{name, age}` after the `{`
+    fn parse_hashmap_binding(&mut self) -> Result<ast::Binding, ParseError> {
+        let start = self.last_span;
+        let mut keys = Vec::new();
+
+        if let Some(Token::CloseBrace) = self.peek_next_token() {
+            self.skip_token().expect("We just peeked");
+            return Ok(ast::Binding::HashMap { keys });
+        };
+
+        loop {
+            keys.push(self.parse_identifier()?);
+
+            match self.next_token() {
+                Some(Token::Comma) => continue,
+                Some(Token::CloseBrace) => break,
+                t => return Err(self.unclosed_delimiter('{', start, t, "`,` or `}`")),
+            };
+        }
+
+        Ok(ast::Binding::HashMap { keys })
+    }
+
     fn parse_return_statement(&mut self) -> Result<ast::Statement, ParseError> {
+        let start = self.last_span;
+        let expression = self.parse_expression(Precedence::Lowest)?;
+        Ok(ast::Statement::ReturnStatement {
+            expression,
+            span: self.span_from(start),
+        })
+    }
+
+    fn parse_assign_statement(
+        &mut self,
+        target: ast::Expression,
+    ) -> Result<ast::Statement, ParseError> {
+        let operation = match self.next_token() {
+            Some(Token::PlusAssign) => Some(ast::InfixOperation::Sum),
+            Some(Token::MinusAssign) => Some(ast::InfixOperation::Subtraction),
+            Some(Token::AsteriskAssign) => Some(ast::InfixOperation::Product),
+            Some(Token::SlashAssign) => Some(ast::InfixOperation::Division),
+            _ => None,
+        };
+
+        let start = target.span();
+        if !is_assignable(&target) {
+            return Err(ParseError::InvalidAssignmentTarget { span: start });
+        }
+
         let expression = self.parse_expression(Precedence::Lowest)?;
-        Ok(ast::Statement::ReturnStatement { expression })
+        let span = self.span_from(start);
+        match target {
+            ast::Expression::IdentifierExpression { identifier, .. } => {
+                Ok(ast::Statement::AssignStatement {
+                    identifier,
+                    operation,
+                    expression,
+                    span,
+                })
+            }
+            ast::Expression::Index { array, index, .. } => {
+                Ok(ast::Statement::IndexAssignStatement {
+                    collection: array,
+                    index,
+                    operation,
+                    expression,
+                    span,
+                })
+            }
+            _ => unreachable!("Checked by is_assignable"),
+        }
+    }
+
+    fn parse_while_statement(&mut self) -> Result<ast::Statement, ParseError> {
+        let start = self.last_span;
+        self.skip_token_expecting(Token::OpenParenthesis)?;
+        let opened = self.last_span;
+        let condition = self.parse_expression(Precedence::Lowest)?;
+        self.skip_closing_delimiter(Token::CloseParenthesis, opened)?;
+
+        self.skip_token_expecting(Token::OpenBrace)?;
+        let opened = self.last_span;
+        self.loop_depth += 1;
+        let body = self.parse_statement_list()?;
+        self.loop_depth -= 1;
+        self.skip_closing_delimiter(Token::CloseBrace, opened)?;
+
+        Ok(ast::Statement::WhileStatement {
+            condition,
+            body,
+            span: self.span_from(start),
+        })
+    }
+
+    fn parse_for_statement(&mut self) -> Result<ast::Statement, ParseError> {
+        let start = self.last_span;
+        self.skip_token_expecting(Token::OpenParenthesis)?;
+        let opened = self.last_span;
+
+        let first = self.parse_identifier()?;
+        let (key, value) = match self.peek_next_token() {
+            Some(Token::Comma) => {
+                self.skip_token().expect("We just peeked");
+                (Some(first), self.parse_identifier()?)
+            }
+            _ => (None, first),
+        };
+
+        self.skip_token_expecting(Token::In)?;
+        let iterable = self.parse_expression(Precedence::Lowest)?;
+        self.skip_closing_delimiter(Token::CloseParenthesis, opened)?;
+
+        self.skip_token_expecting(Token::OpenBrace)?;
+        let opened = self.last_span;
+        self.loop_depth += 1;
+        let body = self.parse_statement_list()?;
+        self.loop_depth -= 1;
+        self.skip_closing_delimiter(Token::CloseBrace, opened)?;
+
+        Ok(ast::Statement::ForStatement {
+            key,
+            value,
+            iterable,
+            body,
+            span: self.span_from(start),
+        })
+    }
+
+    fn parse_loop_control_statement(
+        &mut self,
+        keyword: Token,
+    ) -> Result<ast::Statement, ParseError> {
+        let span = self.last_span;
+        if self.loop_depth == 0 {
+            return Err(ParseError::OutsideOfLoop { keyword, span });
+        }
+
+        match keyword {
+            Token::Break => Ok(ast::Statement::BreakStatement { span }),
+            _ => Ok(ast::Statement::ContinueStatement { span }),
+        }
     }
 
     fn parse_integer_literal_expression(
         &mut self,
         value: String,
     ) -> Result<ast::Expression, ParseError> {
-        match value.parse::<i64>() {
-            Ok(i) => Ok(ast::Expression::IntegerLiteral { value: i }),
-            Err(_) => Err(ParseError::FailedParsingInteger {
+        match parse_integer(&value) {
+            Ok(i) => Ok(ast::Expression::IntegerLiteral {
+                value: i,
+                span: self.last_span,
+            }),
+            Err(reason) => Err(ParseError::FailedParsingInteger {
+                string: value,
+                reason,
+                span: self.last_span,
+            }),
+        }
+    }
+
+    fn parse_float_literal_expression(
+        &mut self,
+        value: String,
+    ) -> Result<ast::Expression, ParseError> {
+        match value.replace('_', "").parse::<f64>() {
+            Ok(f) => Ok(ast::Expression::FloatLiteral {
+                value: f,
+                span: self.last_span,
+            }),
+            Err(_) => Err(ParseError::FailedParsingFloat {
                 string: value,
+                span: self.last_span,
             }),
         }
     }
@@ -140,7 +607,83 @@ impl<T: Iterator<Item = char>> Parser<T> {
         &mut self,
         value: String,
     ) -> Result<ast::Expression, ParseError> {
-        Ok(ast::Expression::StringLiteral { value })
+        Ok(ast::Expression::StringLiteral {
+            value,
+            span: self.last_span,
+        })
+    }
+
+    /// Parses the rest of an interpolated string after its first literal part,
+    /// alternating between expressions and the literal parts between them.
+    fn parse_interpolated_string(&mut self, first: String) -> Result<ast::Expression, ParseError> {
+        let start = self.last_span;
+        let mut parts = Vec::new();
+        let mut literal = first;
+
+        loop {
+            if !literal.is_empty() {
+                parts.push(ast::StringPart::Literal { value: literal });
+            }
+
+            let expression = self.parse_expression(Precedence::Lowest)?;
+            parts.push(ast::StringPart::Expression { expression });
+
+            match self.next_token() {
+                Some(Token::InterpolationMiddle { string }) => literal = string,
+                Some(Token::InterpolationEnd { string }) => {
+                    if !string.is_empty() {
+                        parts.push(ast::StringPart::Literal { value: string });
+                    }
+                    break;
+                }
+                Some(t) => return Err(self.unexpected_token(t, "`}`")),
+                None => return Err(self.unexpected_end()),
+            }
+        }
+
+        Ok(ast::Expression::InterpolatedString {
+            parts,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Parses a statement and the semicolon after it. The returned flag is true
+    /// when the statement has no semicolon, which is only allowed for the last
+    /// one in a block.
+    fn parse_block_statement(&mut self) -> Result<(ast::Statement, bool), ParseError> {
+        let statement = self.parse_statement()?;
+        match self.peek_next_token() {
+            Some(Token::Semicolon) => {
+                self.skip_token()
+                    .expect("We just peeked, so there must be a semicolon here");
+                Ok((statement, false))
+            }
+            Some(t) if t.is_error() => {
+                let token = self.next_token().expect("We just peeked");
+                Err(self.unexpected_token(token, "`;`"))
+            }
+            // Statements that end with a block do not need a semicolon
+            _ if matches!(
+                statement,
+                ast::Statement::WhileStatement { .. } | ast::Statement::ForStatement { .. }
+            ) =>
+            {
+                Ok((statement, false))
+            }
+            _ => {
+                if let ast::Statement::ExpressionStatement { expression, span } = statement {
+                    // An expression without semicolon is the value of the block
+                    return Ok((
+                        ast::Statement::BlockValueStatement { expression, span },
+                        true,
+                    ));
+                }
+                // No-semicolon is not allowed for other types of statements
+                Err(ParseError::MissingSemicolon {
+                    span: statement.span(),
+                })
+            }
+        }
     }
 
     pub fn parse_statement_list(&mut self) -> Result<Vec<ast::Statement>, ParseError> {
@@ -153,41 +696,57 @@ impl<T: Iterator<Item = char>> Parser<T> {
                 _ => {}
             }
 
-            let statement = self.parse_statement()?;
-            match self.peek_next_token() {
-                Some(Token::Semicolon) => {
-                    self.skip_token()
-                        .expect("We just peeked, so there must be a semicolon here");
-                    block.push(statement);
-                }
-                _ => {
-                    if let ast::Statement::ExpressionStatement { expression } = statement {
-                        // If there is no semicolon after statement, and it is a expression,
-                        // transform it to a return statement;
-                        block.push(ast::Statement::ReturnStatement { expression });
-                        break;
-                    }
-                    // No-semicolon is not allowed for other types of statements
-                    return Err(ParseError::MissingSemicolon);
-                }
+            let (statement, is_last) = self.parse_block_statement()?;
+            block.push(statement);
+            if is_last {
+                break;
             }
         }
 
         Ok(block)
     }
 
+    /// Skips the rest of the statement where `error` was found, so parsing can
+    /// continue from the next one. The statement ends at a `;` or `}` that is
+    /// not inside any block opened by the statement itself.
+    fn synchronize(&mut self, error: &ParseError) {
+        let ends_statement = |t: &Token| matches!(t, Token::Semicolon | Token::CloseBrace);
+
+        match error {
+            // The statement is complete, the next one starts right away unless
+            // it was inside a block, which is skipped up to its closing `}`
+            ParseError::MissingSemicolon { .. } if self.depth <= 0 => return,
+            // The token that caused the error already ended the statement
+            ParseError::UnexpectedToken { token, .. }
+            | ParseError::UnclosedDelimiter {
+                token: Some(token), ..
+            } if self.depth <= 0 && ends_statement(token) => return,
+            _ => {}
+        }
+
+        while let Some(token) = self.next_token() {
+            if self.depth <= 0 && ends_statement(&token) {
+                return;
+            }
+        }
+    }
+
     fn parse_hashmap(&mut self) -> Result<ast::Expression, ParseError> {
         let mut pairs: Vec<(ast::Expression, ast::Expression)> = Vec::new();
         self.skip_token_expecting(Token::OpenBrace)?;
+        let start = self.last_span;
 
         if let Some(Token::CloseBrace) = self.peek_next_token() {
             self.skip_token().expect("We just peeked");
-            return Ok(ast::Expression::HashMap { pairs });
+            return Ok(ast::Expression::HashMap {
+                pairs,
+                span: self.span_from(start),
+            });
         };
 
         loop {
             let key = self.parse_expression(Precedence::Lowest)?;
-        self.skip_token_expecting(Token::Colon)?;
+            self.skip_token_expecting(Token::Colon)?;
             let value = self.parse_expression(Precedence::Lowest)?;
 
             pairs.push((key, value));
@@ -201,26 +760,30 @@ impl<T: Iterator<Item = char>> Parser<T> {
                     self.skip_token().expect("We just peeked");
                     break;
                 }
-                Some(t) => {
-                    return Err(ParseError::UnexpectedToken {
-                        token: t.clone(),
-                        expecting: String::from("comma, close brace"),
-                    })
+                _ => {
+                    let token = self.next_token();
+                    return Err(self.unclosed_delimiter('{', start, token, "`,` or `}`"));
                 }
-                None => return Err(ParseError::UnexpectedEnd),
             };
         }
 
-        Ok(ast::Expression::HashMap { pairs })
+        Ok(ast::Expression::HashMap {
+            pairs,
+            span: self.span_from(start),
+        })
     }
 
     fn parse_array(&mut self) -> Result<ast::Expression, ParseError> {
         let mut list: Vec<ast::Expression> = Vec::new();
         self.skip_token_expecting(Token::OpenSquare)?;
+        let start = self.last_span;
 
         if let Some(Token::CloseSquare) = self.peek_next_token() {
             self.skip_token().expect("We just peeked");
-            return Ok(ast::Expression::Array { array: list });
+            return Ok(ast::Expression::Array {
+                array: list,
+                span: self.span_from(start),
+            });
         };
 
         loop {
@@ -237,32 +800,51 @@ impl<T: Iterator<Item = char>> Parser<T> {
                     self.skip_token().expect("We just peeked");
                     break;
                 }
-                Some(t) => {
-                    return Err(ParseError::UnexpectedToken {
-                        token: t.clone(),
-                        expecting: String::from("comma, close square"),
-                    })
+                _ => {
+                    let token = self.next_token();
+                    return Err(self.unclosed_delimiter('[', start, token, "`,` or `]`"));
                 }
-                None => return Err(ParseError::UnexpectedEnd),
             };
         }
 
-        Ok(ast::Expression::Array { array: list })
+        Ok(ast::Expression::Array {
+            array: list,
+            span: self.span_from(start),
+        })
     }
 
-    fn parse_expression_list(&mut self) -> Result<Vec<ast::Expression>, ParseError> {
-        let mut list: Vec<ast::Expression> = Vec::new();
+    // Parses `(x, y, key: value)`, returning the positional and the keyword
+    // arguments
+    #[allow(clippy::type_complexity)]
+    fn parse_call_arguments(
+        &mut self,
+    ) -> Result<(Vec<ast::Expression>, Vec<(String, ast::Expression)>), ParseError> {
+        let mut arguments: Vec<ast::Expression> = Vec::new();
+        let mut keyword_arguments: Vec<(String, ast::Expression)> = Vec::new();
         self.skip_token_expecting(Token::OpenParenthesis)?;
+        let start = self.last_span;
 
         if let Some(Token::CloseParenthesis) = self.peek_next_token() {
             self.skip_token().expect("We just peeked");
-            return Ok(list);
+            return Ok((arguments, keyword_arguments));
         };
 
         loop {
             let expression = self.parse_expression(Precedence::Lowest)?;
 
-            list.push(expression);
+            match (expression, self.peek_next_token()) {
+                (ast::Expression::IdentifierExpression { identifier, .. }, Some(Token::Colon)) => {
+                    self.skip_token().expect("We just peeked");
+                    let value = self.parse_expression(Precedence::Lowest)?;
+                    keyword_arguments.push((identifier, value));
+                }
+                (expression, _) if !keyword_arguments.is_empty() => {
+                    return Err(ParseError::PositionalAfterKeywordArgument {
+                        span: expression.span(),
+                    });
+                }
+                (expression, _) => arguments.push(expression),
+            }
 
             match self.peek_next_token() {
                 Some(Token::Comma) => {
@@ -273,67 +855,140 @@ impl<T: Iterator<Item = char>> Parser<T> {
                     self.skip_token().expect("We just peeked");
                     break;
                 }
-                Some(t) => {
-                    return Err(ParseError::UnexpectedToken {
-                        token: t.clone(),
-                        expecting: String::from("comma, close parenthesis"),
-                    })
+                _ => {
+                    let token = self.next_token();
+                    return Err(self.unclosed_delimiter('(', start, token, "`,` or `)`"));
                 }
-                None => return Err(ParseError::UnexpectedEnd),
             };
         }
 
-        Ok(list)
+        Ok((arguments, keyword_arguments))
     }
 
-    fn parse_function_expression(&mut self) -> Result<ast::Expression, ParseError> {
-        let arguments_expressions = self.parse_expression_list()?;
+    // Parses `(a, b = 10, ...rest)`, returning the parameters and the name of
+    // the rest parameter
+    fn parse_function_parameters(
+        &mut self,
+    ) -> Result<(Vec<ast::Parameter>, Option<String>), ParseError> {
+        let mut parameters: Vec<ast::Parameter> = Vec::new();
+        let mut rest = None;
+        self.skip_token_expecting(Token::OpenParenthesis)?;
+        let start = self.last_span;
 
-        if !arguments_expressions
-            .iter()
-            .all(|ex| matches!(ex, ast::Expression::IdentifierExpression { .. }))
-        {
-            return Err(ParseError::NonIdentifierExpression);
-        }
+        if let Some(Token::CloseParenthesis) = self.peek_next_token() {
+            self.skip_token().expect("We just peeked");
+            return Ok((parameters, rest));
+        };
 
-        let arguments = arguments_expressions
-            .into_iter()
-            .map(|ex| {
-                if let ast::Expression::IdentifierExpression { identifier } = ex {
-                    return identifier;
+        let check_duplicate = |parameters: &[ast::Parameter], name: &str, span: Span| {
+            if parameters.iter().any(|p| p.name == name) {
+                return Err(ParseError::DuplicateParameter {
+                    name: String::from(name),
+                    span,
+                });
+            }
+            Ok(())
+        };
+
+        loop {
+            if let Some(Token::Ellipsis) = self.peek_next_token() {
+                self.skip_token().expect("We just peeked");
+                let name = self.parse_identifier()?;
+                check_duplicate(&parameters, &name, self.last_span)?;
+                rest = Some(name);
+                self.skip_closing_delimiter(Token::CloseParenthesis, start)?;
+                break;
+            }
+
+            let (name, span) = match self.parse_expression(Precedence::Lowest)? {
+                ast::Expression::IdentifierExpression { identifier, span } => (identifier, span),
+                ex => return Err(ParseError::NonIdentifierExpression { span: ex.span() }),
+            };
+            check_duplicate(&parameters, &name, span)?;
+            let default = match self.peek_next_token() {
+                Some(Token::Assign) => {
+                    self.skip_token().expect("We just peeked");
+                    Some(self.parse_expression(Precedence::Lowest)?)
+                }
+                _ if parameters.iter().any(|p| p.default.is_some()) => {
+                    return Err(ParseError::RequiredAfterDefaultParameter { span })
                 }
+                _ => None,
+            };
+            parameters.push(ast::Parameter { name, default });
+
+            match self.next_token() {
+                Some(Token::Comma) => continue,
+                Some(Token::CloseParenthesis) => break,
+                t => return Err(self.unclosed_delimiter('(', start, t, "`,` or `)`")),
+            };
+        }
 
-                unreachable!();
-            })
-            .collect::<Vec<_>>();
+        Ok((parameters, rest))
+    }
+
+    fn parse_function_expression(&mut self) -> Result<ast::Expression, ParseError> {
+        let start = self.last_span;
 
+        // The parameters and body of a function are not inside the loops
+        // around the function
+        let loop_depth = std::mem::replace(&mut self.loop_depth, 0);
+        let (arguments, rest) = self.parse_function_parameters()?;
         self.skip_token_expecting(Token::OpenBrace)?;
+        let opened = self.last_span;
         let body: Vec<ast::Statement> = self.parse_statement_list()?;
-        self.skip_token_expecting(Token::CloseBrace)?;
+        self.skip_closing_delimiter(Token::CloseBrace, opened)?;
+        self.loop_depth = loop_depth;
 
-        Ok(ast::Expression::FunctionExpression { arguments, body })
+        Ok(ast::Expression::FunctionExpression {
+            arguments,
+            rest,
+            body,
+            span: self.span_from(start),
+        })
     }
 
     fn parse_if_expression(&mut self) -> Result<ast::Expression, ParseError> {
+        let start = self.last_span;
         self.skip_token_expecting(Token::OpenParenthesis)?;
+        let opened = self.last_span;
         let condition = self.parse_expression(Precedence::Lowest)?;
-        self.skip_token_expecting(Token::CloseParenthesis)?;
+        self.skip_closing_delimiter(Token::CloseParenthesis, opened)?;
 
         self.skip_token_expecting(Token::OpenBrace)?;
+        let opened = self.last_span;
         let consequence: Vec<ast::Statement> = self.parse_statement_list()?;
-        self.skip_token_expecting(Token::CloseBrace)?;
+        self.skip_closing_delimiter(Token::CloseBrace, opened)?;
 
         match self.peek_next_token() {
             Some(Token::Else) => {
                 self.skip_token()?;
+
+                // `else if (...) { ... }` is parsed as `else { if (...) { ... } }`
+                if let Some(Token::If) = self.peek_next_token() {
+                    self.skip_token().expect("We just peeked");
+                    let nested = self.parse_if_expression()?;
+                    return Ok(ast::Expression::IfExpression {
+                        condition: Box::new(condition),
+                        consequence,
+                        alternative: Some(vec![ast::Statement::BlockValueStatement {
+                            span: nested.span(),
+                            expression: nested,
+                        }]),
+                        span: self.span_from(start),
+                    });
+                }
+
                 self.skip_token_expecting(Token::OpenBrace)?;
+                let opened = self.last_span;
                 let alternative: Vec<ast::Statement> = self.parse_statement_list()?;
-                self.skip_token_expecting(Token::CloseBrace)?;
+                self.skip_closing_delimiter(Token::CloseBrace, opened)?;
 
                 Ok(ast::Expression::IfExpression {
                     condition: Box::new(condition),
                     consequence,
                     alternative: Some(alternative),
+                    span: self.span_from(start),
                 })
             }
 
@@ -341,14 +996,181 @@ impl<T: Iterator<Item = char>> Parser<T> {
                 condition: Box::new(condition),
                 consequence,
                 alternative: None,
+                span: self.span_from(start),
             }),
         }
     }
 
+    fn parse_match_expression(&mut self) -> Result<ast::Expression, ParseError> {
+        let start = self.last_span;
+        self.skip_token_expecting(Token::OpenParenthesis)?;
+        let opened = self.last_span;
+        let value = self.parse_expression(Precedence::Lowest)?;
+        self.skip_closing_delimiter(Token::CloseParenthesis, opened)?;
+
+        self.skip_token_expecting(Token::OpenBrace)?;
+        let opened = self.last_span;
+        let mut arms = Vec::new();
+
+        loop {
+            if let Some(Token::CloseBrace) = self.peek_next_token() {
+                self.skip_token().expect("We just peeked");
+                break;
+            }
+
+            arms.push(self.parse_match_arm()?);
+
+            match self.next_token() {
+                Some(Token::Comma) => continue,
+                Some(Token::CloseBrace) => break,
+                t => return Err(self.unclosed_delimiter('{', opened, t, "`,` or `}`")),
+            };
+        }
+
+        Ok(ast::Expression::MatchExpression {
+            value: Box::new(value),
+            arms,
+            span: self.span_from(start),
+        })
+    }
+
+    fn parse_match_arm(&mut self) -> Result<ast::MatchArm, ParseError> {
+        let pattern = self.parse_pattern()?;
+
+        let guard = match self.peek_next_token() {
+            Some(Token::If) => {
+                self.skip_token().expect("We just peeked");
+                Some(self.parse_expression(Precedence::Lowest)?)
+            }
+            _ => None,
+        };
+
+        self.skip_token_expecting(Token::FatArrow)?;
+        let body = self.parse_expression(Precedence::Lowest)?;
+
+        Ok(ast::MatchArm {
+            pattern,
+            guard,
+            body,
+        })
+    }
+
+    fn parse_pattern(&mut self) -> Result<ast::Pattern, ParseError> {
+        let value = match self.next_token() {
+            Some(Token::Identifier { name }) if name == "_" => return Ok(ast::Pattern::Wildcard),
+            Some(Token::Identifier { name }) => return Ok(ast::Pattern::Identifier { name }),
+            Some(Token::OpenSquare) => return self.parse_array_pattern(),
+            Some(Token::OpenBrace) => return self.parse_hashmap_pattern(),
+            Some(Token::Integer { string }) => self.parse_integer_literal_expression(string)?,
+            Some(Token::Float { string }) => self.parse_float_literal_expression(string)?,
+            Some(Token::StringLiteral { string }) => {
+                self.parse_string_literal_expression(string)?
+            }
+            Some(Token::True) => ast::Expression::Boolean {
+                value: true,
+                span: self.last_span,
+            },
+            Some(Token::False) => ast::Expression::Boolean {
+                value: false,
+                span: self.last_span,
+            },
+            Some(Token::Minus) => self.parse_negative_number_pattern()?,
+            Some(t) => return Err(self.unexpected_token(t, "pattern")),
+            None => return Err(self.unexpected_end()),
+        };
+
+        Ok(ast::Pattern::Literal { value })
+    }
+
+    // Parses the number after the `-` of a literal pattern
+    fn parse_negative_number_pattern(&mut self) -> Result<ast::Expression, ParseError> {
+        let start = self.last_span;
+        let number = match self.next_token() {
+            Some(Token::Integer { string }) => self.parse_integer_literal_expression(string)?,
+            Some(Token::Float { string }) => self.parse_float_literal_expression(string)?,
+            Some(t) => return Err(self.unexpected_token(t, "number")),
+            None => return Err(self.unexpected_end()),
+        };
+
+        Ok(ast::Expression::PrefixExpression {
+            operation: ast::PrefixOperation::Negative,
+            right: Box::new(number),
+            span: self.span_from(start),
+        })
+    }
+
+    // Parses `[a, [b, _], ...rest]` after the `[`
+    fn parse_array_pattern(&mut self) -> Result<ast::Pattern, ParseError> {
+        let start = self.last_span;
+        let mut elements = Vec::new();
+        let mut rest = None;
+
+        if let Some(Token::CloseSquare) = self.peek_next_token() {
+            self.skip_token().expect("We just peeked");
+            return Ok(ast::Pattern::Array { elements, rest });
+        };
+
+        loop {
+            if let Some(Token::Ellipsis) = self.peek_next_token() {
+                self.skip_token().expect("We just peeked");
+                rest = Some(self.parse_identifier()?);
+                self.skip_closing_delimiter(Token::CloseSquare, start)?;
+                break;
+            }
+
+            elements.push(self.parse_pattern()?);
+
+            match self.next_token() {
+                Some(Token::Comma) => continue,
+                Some(Token::CloseSquare) => break,
+                t => return Err(self.unclosed_delimiter('[', start, t, "`,` or `]`")),
+            };
+        }
+
+        Ok(ast::Pattern::Array { elements, rest })
+    }
+
+    // Parses `{name, "age": 30, role: r}` after the `{`
+    fn parse_hashmap_pattern(&mut self) -> Result<ast::Pattern, ParseError> {
+        let start = self.last_span;
+        let mut pairs = Vec::new();
+
+        if let Some(Token::CloseBrace) = self.peek_next_token() {
+            self.skip_token().expect("We just peeked");
+            return Ok(ast::Pattern::HashMap { pairs });
+        };
+
+        loop {
+            let (key, shorthand) = match self.next_token() {
+                Some(Token::Identifier { name }) => (name, true),
+                Some(Token::StringLiteral { string }) => (string, false),
+                Some(t) => return Err(self.unexpected_token(t, "identifier or string")),
+                None => return Err(self.unexpected_end()),
+            };
+
+            let pattern = if shorthand && self.peek_next_token() != Some(&Token::Colon) {
+                ast::Pattern::Identifier { name: key.clone() }
+            } else {
+                self.skip_token_expecting(Token::Colon)?;
+                self.parse_pattern()?
+            };
+            pairs.push((key, pattern));
+
+            match self.next_token() {
+                Some(Token::Comma) => continue,
+                Some(Token::CloseBrace) => break,
+                t => return Err(self.unclosed_delimiter('{', start, t, "`,` or `}`")),
+            };
+        }
+
+        Ok(ast::Pattern::HashMap { pairs })
+    }
+
     fn parse_grouped_expression(&mut self) -> Result<ast::Expression, ParseError> {
+        let opened = self.last_span;
         let expression = self.parse_expression(Precedence::Lowest)?;
 
-        self.skip_token_expecting(Token::CloseParenthesis)?;
+        self.skip_closing_delimiter(Token::CloseParenthesis, opened)?;
 
         Ok(expression)
     }
@@ -357,11 +1179,13 @@ impl<T: Iterator<Item = char>> Parser<T> {
         &mut self,
         operation: ast::PrefixOperation,
     ) -> Result<ast::Expression, ParseError> {
+        let start = self.last_span;
         let expression = self.parse_expression(Precedence::Prefix)?;
 
         Ok(ast::Expression::PrefixExpression {
             operation,
             right: Box::new(expression),
+            span: self.span_from(start),
         })
     }
 
@@ -369,33 +1193,41 @@ impl<T: Iterator<Item = char>> Parser<T> {
         if let Some(token) = self.next_token() {
             return match token {
                 Token::Integer { string } => self.parse_integer_literal_expression(string),
+                Token::Float { string } => self.parse_float_literal_expression(string),
                 Token::StringLiteral { string } => self.parse_string_literal_expression(string),
-                Token::Identifier { name } => {
-                    Ok(ast::Expression::IdentifierExpression { identifier: name })
-                }
-                Token::True => Ok(ast::Expression::Boolean { value: true }),
-                Token::False => Ok(ast::Expression::Boolean { value: false }),
+                Token::InterpolationStart { string } => self.parse_interpolated_string(string),
+                Token::Identifier { name } => Ok(ast::Expression::IdentifierExpression {
+                    identifier: name,
+                    span: self.last_span,
+                }),
+                Token::True => Ok(ast::Expression::Boolean {
+                    value: true,
+                    span: self.last_span,
+                }),
+                Token::False => Ok(ast::Expression::Boolean {
+                    value: false,
+                    span: self.last_span,
+                }),
                 Token::Bang => self.parse_prefix_expression(ast::PrefixOperation::Negate),
                 Token::Minus => self.parse_prefix_expression(ast::PrefixOperation::Negative),
+                Token::Tilde => self.parse_prefix_expression(ast::PrefixOperation::BitNot),
                 Token::OpenParenthesis => self.parse_grouped_expression(),
                 Token::OpenSquare => {
                     self.save_token(token);
                     self.parse_array()
                 }
-                Token::OpenBrace=> {
+                Token::OpenBrace => {
                     self.save_token(token);
                     self.parse_hashmap()
                 }
                 Token::If => self.parse_if_expression(),
+                Token::Match => self.parse_match_expression(),
                 Token::Function => self.parse_function_expression(),
-                t => Err(ParseError::UnexpectedToken {
-                    token: t,
-                    expecting: String::from("Prefix operator/Integer/Identifier"),
-                }),
+                t => Err(self.unexpected_token(t, "expression")),
             };
         }
 
-        Err(ParseError::UnexpectedEnd)
+        Err(self.unexpected_end())
     }
 
     fn parse_index_expression(
@@ -403,9 +1235,11 @@ impl<T: Iterator<Item = char>> Parser<T> {
         array: ast::Expression,
     ) -> Result<ast::Expression, ParseError> {
         self.skip_token_expecting(Token::OpenSquare)?;
+        let opened = self.last_span;
         let index = self.parse_expression(Precedence::Lowest)?;
-        self.skip_token_expecting(Token::CloseSquare)?;
+        self.skip_closing_delimiter(Token::CloseSquare, opened)?;
         Ok(ast::Expression::Index {
+            span: self.span_from(array.span()),
             array: Box::new(array),
             index: Box::new(index),
         })
@@ -415,10 +1249,12 @@ impl<T: Iterator<Item = char>> Parser<T> {
         &mut self,
         function: ast::Expression,
     ) -> Result<ast::Expression, ParseError> {
-        let arguments = self.parse_expression_list()?;
+        let (arguments, keyword_arguments) = self.parse_call_arguments()?;
         Ok(ast::Expression::CallExpression {
+            span: self.span_from(function.span()),
             function: Box::new(function),
             arguments,
+            keyword_arguments,
         })
     }
 
@@ -427,10 +1263,15 @@ impl<T: Iterator<Item = char>> Parser<T> {
         operation: ast::InfixOperation,
         left: ast::Expression,
     ) -> Result<ast::Expression, ParseError> {
-        let precedence = infix_operator_precedence(&operation);
+        // `**` is right associative: `2 ** 3 ** 2` is `2 ** (3 ** 2)`
+        let precedence = match operation {
+            ast::InfixOperation::Power => Precedence::Prefix,
+            _ => infix_operator_precedence(&operation),
+        };
         match self.parse_expression(precedence) {
             Ok(exp) => Ok(ast::Expression::InfixExpression {
                 operation,
+                span: left.span().to(exp.span()),
                 left: Box::new(left),
                 right: Box::new(exp),
             }),
@@ -455,6 +1296,17 @@ impl<T: Iterator<Item = char>> Parser<T> {
                 Token::GreaterThan => ast::InfixOperation::GreaterThan,
                 Token::LessThanEqual => ast::InfixOperation::LessThanEqual,
                 Token::GreaterThanEqual => ast::InfixOperation::GreaterThanEqual,
+                Token::And => ast::InfixOperation::And,
+                Token::Or => ast::InfixOperation::Or,
+                Token::Percent => ast::InfixOperation::Modulo,
+                Token::Power => ast::InfixOperation::Power,
+                Token::Ampersand => ast::InfixOperation::BitAnd,
+                Token::Pipe => ast::InfixOperation::BitOr,
+                Token::Caret => ast::InfixOperation::BitXor,
+                Token::ShiftLeft => ast::InfixOperation::ShiftLeft,
+                Token::ShiftRight => ast::InfixOperation::ShiftRight,
+                Token::DotDot => ast::InfixOperation::Range,
+                Token::DotDotEqual => ast::InfixOperation::RangeInclusive,
                 Token::OpenParenthesis => {
                     self.save_token(token);
                     return Some(self.parse_call_expression(left));
@@ -505,21 +1357,89 @@ impl<T: Iterator<Item = char>> Parser<T> {
             return match token {
                 Token::Let => self.parse_let_statement(),
                 Token::Return => self.parse_return_statement(),
+                Token::While => self.parse_while_statement(),
+                Token::For => self.parse_for_statement(),
+                Token::Break | Token::Continue => self.parse_loop_control_statement(token),
                 t => {
                     // Try to parse expression as ExpressionStatement
                     self.save_token(t);
-                    match self.parse_expression(Precedence::Lowest) {
-                        Ok(expression) => Ok(ast::Statement::ExpressionStatement { expression }),
-                        Err(e) => Err(e),
+                    let expression = self.parse_expression(Precedence::Lowest)?;
+                    match self.peek_next_token() {
+                        Some(
+                            Token::Assign
+                            | Token::PlusAssign
+                            | Token::MinusAssign
+                            | Token::AsteriskAssign
+                            | Token::SlashAssign,
+                        ) => self.parse_assign_statement(expression),
+                        _ => Ok(ast::Statement::ExpressionStatement {
+                            span: expression.span(),
+                            expression,
+                        }),
                     }
                 }
             };
         }
 
-        Err(ParseError::UnexpectedEnd)
+        Err(self.unexpected_end())
     }
 
     pub fn parse_program(&mut self) -> Result<ast::Program, ParseError> {
-        self.parse_statement_list()
+        let (program, mut errors) = self.parse_program_recovering();
+
+        if errors.is_empty() {
+            return Ok(program);
+        }
+
+        Err(errors.remove(0))
+    }
+
+    /// Parses the whole input even if it has errors, skipping the statements
+    /// that can not be parsed. Returns the statements that could be parsed
+    /// together with all the errors found.
+    pub fn parse_program_recovering(&mut self) -> (ast::Program, Vec<ParseError>) {
+        let mut program: ast::Program = Vec::new();
+        let mut errors: Vec<ParseError> = Vec::new();
+        let mut recovering = false;
+
+        loop {
+            self.depth = 0;
+            self.loop_depth = 0;
+
+            match self.peek_next_token() {
+                None => break,
+                // Left over from a statement skipped while recovering
+                Some(Token::Semicolon) if recovering => {
+                    self.skip_token().expect("We just peeked");
+                    continue;
+                }
+                Some(Token::CloseBrace) => {
+                    let token = self.next_token().expect("We just peeked");
+                    errors.push(self.unexpected_token(token, "statement"));
+                    recovering = true;
+                    continue;
+                }
+                _ => {}
+            }
+
+            match self.parse_block_statement() {
+                Ok((statement, is_last)) => {
+                    if is_last && self.peek_next_token().is_some() {
+                        errors.push(ParseError::MissingSemicolon {
+                            span: statement.span(),
+                        });
+                    }
+                    program.push(statement);
+                    recovering = false;
+                }
+                Err(e) => {
+                    self.synchronize(&e);
+                    errors.push(e);
+                    recovering = true;
+                }
+            }
+        }
+
+        (program, errors)
     }
 }
@@ -1,5 +1,13 @@
 use parser::ast;
-use parser::Parser;
+use parser::lexer::Token;
+use parser::{IntegerError, ParseError, Parser, Span};
+
+fn parameter(name: &str) -> ast::Parameter {
+    ast::Parameter {
+        name: String::from(name),
+        default: None,
+    }
+}
 
 fn parse(s: &str) -> ast::Program {
     let mut parser = Parser::new(s.chars());
@@ -11,18 +19,185 @@ fn parse_errors(s: &str) -> bool {
     parser.parse_program().is_err()
 }
 
+// Expected ASTs are written with `Span::default()` everywhere, so parsed ones
+// are compared after resetting their spans in the same way
+fn clear_statement_spans(statement: &mut ast::Statement) {
+    use ast::Statement::*;
+
+    match statement {
+        LetStatement {
+            expression, span, ..
+        }
+        | ReturnStatement { expression, span }
+        | AssignStatement {
+            expression, span, ..
+        }
+        | ExpressionStatement { expression, span }
+        | BlockValueStatement { expression, span } => {
+            clear_expression_spans(expression);
+            *span = Span::default();
+        }
+        IndexAssignStatement {
+            collection,
+            index,
+            expression,
+            span,
+            ..
+        } => {
+            clear_expression_spans(collection);
+            clear_expression_spans(index);
+            clear_expression_spans(expression);
+            *span = Span::default();
+        }
+        WhileStatement {
+            condition: expression,
+            body,
+            span,
+        }
+        | ForStatement {
+            iterable: expression,
+            body,
+            span,
+            ..
+        } => {
+            clear_expression_spans(expression);
+            body.iter_mut().for_each(clear_statement_spans);
+            *span = Span::default();
+        }
+        BreakStatement { span } | ContinueStatement { span } => *span = Span::default(),
+    }
+}
+
+fn clear_expression_spans(expression: &mut ast::Expression) {
+    use ast::Expression::*;
+
+    match expression {
+        IntegerLiteral { span, .. }
+        | FloatLiteral { span, .. }
+        | StringLiteral { span, .. }
+        | IdentifierExpression { span, .. }
+        | Boolean { span, .. } => *span = Span::default(),
+        InterpolatedString { parts, span } => {
+            for part in parts {
+                if let ast::StringPart::Expression { expression } = part {
+                    clear_expression_spans(expression);
+                }
+            }
+            *span = Span::default();
+        }
+        Array { array, span } => {
+            array.iter_mut().for_each(clear_expression_spans);
+            *span = Span::default();
+        }
+        HashMap { pairs, span } => {
+            for (key, value) in pairs {
+                clear_expression_spans(key);
+                clear_expression_spans(value);
+            }
+            *span = Span::default();
+        }
+        PrefixExpression { right, span, .. } => {
+            clear_expression_spans(right);
+            *span = Span::default();
+        }
+        InfixExpression {
+            left, right, span, ..
+        }
+        | Index {
+            array: left,
+            index: right,
+            span,
+        } => {
+            clear_expression_spans(left);
+            clear_expression_spans(right);
+            *span = Span::default();
+        }
+        IfExpression {
+            condition,
+            consequence,
+            alternative,
+            span,
+        } => {
+            clear_expression_spans(condition);
+            consequence.iter_mut().for_each(clear_statement_spans);
+            for statement in alternative.iter_mut().flatten() {
+                clear_statement_spans(statement);
+            }
+            *span = Span::default();
+        }
+        MatchExpression { value, arms, span } => {
+            clear_expression_spans(value);
+            for arm in arms {
+                clear_pattern_spans(&mut arm.pattern);
+                arm.guard.iter_mut().for_each(clear_expression_spans);
+                clear_expression_spans(&mut arm.body);
+            }
+            *span = Span::default();
+        }
+        FunctionExpression {
+            arguments,
+            body,
+            span,
+            ..
+        } => {
+            for argument in arguments {
+                argument.default.iter_mut().for_each(clear_expression_spans);
+            }
+            body.iter_mut().for_each(clear_statement_spans);
+            *span = Span::default();
+        }
+        CallExpression {
+            function,
+            arguments,
+            keyword_arguments,
+            span,
+        } => {
+            clear_expression_spans(function);
+            arguments.iter_mut().for_each(clear_expression_spans);
+            for (_, argument) in keyword_arguments {
+                clear_expression_spans(argument);
+            }
+            *span = Span::default();
+        }
+    }
+}
+
+fn clear_pattern_spans(pattern: &mut ast::Pattern) {
+    match pattern {
+        ast::Pattern::Literal { value } => clear_expression_spans(value),
+        ast::Pattern::Array { elements, .. } => elements.iter_mut().for_each(clear_pattern_spans),
+        ast::Pattern::HashMap { pairs } => {
+            for (_, pattern) in pairs {
+                clear_pattern_spans(pattern);
+            }
+        }
+        ast::Pattern::Wildcard | ast::Pattern::Identifier { .. } => {}
+    }
+}
+
+#[track_caller]
+fn assert_same_ast(mut left: ast::Program, mut right: ast::Program) {
+    left.iter_mut().for_each(clear_statement_spans);
+    right.iter_mut().for_each(clear_statement_spans);
+    assert_eq!(left, right);
+}
+
 #[test]
 fn test_let_statement() {
     let program = "let answer = \"hola\";";
 
     let expected_ast = vec![ast::Statement::LetStatement {
-        identifier: String::from("answer"),
+        binding: ast::Binding::Identifier {
+            name: String::from("answer"),
+        },
         expression: ast::Expression::StringLiteral {
             value: String::from("hola"),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -30,21 +205,29 @@ fn test_return_expression() {
     let program = "return 12;";
 
     let expected_ast = vec![ast::Statement::ReturnStatement {
-        expression: ast::Expression::IntegerLiteral { value: 12 },
+        expression: ast::Expression::IntegerLiteral {
+            value: 12,
+            span: Span::default(),
+        },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
-fn test_implicit_return_expression() {
+fn test_block_value_expression() {
     let program = "12";
 
-    let expected_ast = vec![ast::Statement::ReturnStatement {
-        expression: ast::Expression::IntegerLiteral { value: 12 },
+    let expected_ast = vec![ast::Statement::BlockValueStatement {
+        expression: ast::Expression::IntegerLiteral {
+            value: 12,
+            span: Span::default(),
+        },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -58,22 +241,36 @@ fn test_expression_statement() {
 
     let expected_ast = vec![
         ast::Statement::ExpressionStatement {
-            expression: ast::Expression::IntegerLiteral { value: 42 },
+            expression: ast::Expression::IntegerLiteral {
+                value: 42,
+                span: Span::default(),
+            },
+            span: Span::default(),
         },
         ast::Statement::ExpressionStatement {
             expression: ast::Expression::IdentifierExpression {
                 identifier: String::from("mandarina"),
+                span: Span::default(),
             },
+            span: Span::default(),
         },
         ast::Statement::ExpressionStatement {
-            expression: ast::Expression::Boolean { value: true },
+            expression: ast::Expression::Boolean {
+                value: true,
+                span: Span::default(),
+            },
+            span: Span::default(),
         },
         ast::Statement::ExpressionStatement {
-            expression: ast::Expression::Boolean { value: false },
+            expression: ast::Expression::Boolean {
+                value: false,
+                span: Span::default(),
+            },
+            span: Span::default(),
         },
     ];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -89,18 +286,26 @@ fn test_prefix_expressions() {
                 operation: ast::PrefixOperation::Negate,
                 right: Box::new(ast::Expression::IdentifierExpression {
                     identifier: String::from("n"),
+                    span: Span::default(),
                 }),
+                span: Span::default(),
             },
+            span: Span::default(),
         },
         ast::Statement::ExpressionStatement {
             expression: ast::Expression::PrefixExpression {
                 operation: ast::PrefixOperation::Negative,
-                right: Box::new(ast::Expression::IntegerLiteral { value: 22 }),
+                right: Box::new(ast::Expression::IntegerLiteral {
+                    value: 22,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             },
+            span: Span::default(),
         },
     ];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -110,11 +315,19 @@ fn test_sum_expression() {
     let expected_ast = vec![ast::Statement::ExpressionStatement {
         expression: ast::Expression::InfixExpression {
             operation: ast::InfixOperation::Sum,
-            left: Box::new(ast::Expression::IntegerLiteral { value: 42 }),
-            right: Box::new(ast::Expression::IntegerLiteral { value: 4 }),
+            left: Box::new(ast::Expression::IntegerLiteral {
+                value: 42,
+                span: Span::default(),
+            }),
+            right: Box::new(ast::Expression::IntegerLiteral {
+                value: 4,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -124,11 +337,19 @@ fn test_subtraction_expressions() {
     let expected_ast = vec![ast::Statement::ExpressionStatement {
         expression: ast::Expression::InfixExpression {
             operation: ast::InfixOperation::Subtraction,
-            left: Box::new(ast::Expression::IntegerLiteral { value: 42 }),
-            right: Box::new(ast::Expression::IntegerLiteral { value: 4 }),
+            left: Box::new(ast::Expression::IntegerLiteral {
+                value: 42,
+                span: Span::default(),
+            }),
+            right: Box::new(ast::Expression::IntegerLiteral {
+                value: 4,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 #[test]
 fn test_division_expressions() {
@@ -139,13 +360,17 @@ fn test_division_expressions() {
             operation: ast::InfixOperation::Division,
             left: Box::new(ast::Expression::IdentifierExpression {
                 identifier: String::from("mandarina"),
+                span: Span::default(),
             }),
             right: Box::new(ast::Expression::IdentifierExpression {
                 identifier: String::from("platan"),
+                span: Span::default(),
             }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 #[test]
 fn test_product_expressions() {
@@ -156,11 +381,17 @@ fn test_product_expressions() {
             operation: ast::InfixOperation::Product,
             left: Box::new(ast::Expression::IdentifierExpression {
                 identifier: String::from("mandarina"),
+                span: Span::default(),
             }),
-            right: Box::new(ast::Expression::IntegerLiteral { value: 2 }),
+            right: Box::new(ast::Expression::IntegerLiteral {
+                value: 2,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 #[test]
 fn test_equal_expressions() {
@@ -171,11 +402,17 @@ fn test_equal_expressions() {
             operation: ast::InfixOperation::Equal,
             left: Box::new(ast::Expression::IdentifierExpression {
                 identifier: String::from("mandarina"),
+                span: Span::default(),
+            }),
+            right: Box::new(ast::Expression::IntegerLiteral {
+                value: 52,
+                span: Span::default(),
             }),
-            right: Box::new(ast::Expression::IntegerLiteral { value: 52 }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 #[test]
 fn test_not_equal_expressions() {
@@ -186,11 +423,17 @@ fn test_not_equal_expressions() {
             operation: ast::InfixOperation::NotEqual,
             left: Box::new(ast::Expression::IdentifierExpression {
                 identifier: String::from("mandarina"),
+                span: Span::default(),
+            }),
+            right: Box::new(ast::Expression::IntegerLiteral {
+                value: 51,
+                span: Span::default(),
             }),
-            right: Box::new(ast::Expression::IntegerLiteral { value: 51 }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 #[test]
 fn test_less_than_expressions() {
@@ -201,11 +444,17 @@ fn test_less_than_expressions() {
             operation: ast::InfixOperation::LessThan,
             left: Box::new(ast::Expression::IdentifierExpression {
                 identifier: String::from("mandarina"),
+                span: Span::default(),
             }),
-            right: Box::new(ast::Expression::IntegerLiteral { value: 51 }),
+            right: Box::new(ast::Expression::IntegerLiteral {
+                value: 51,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 #[test]
 fn test_greater_than_expressions() {
@@ -214,13 +463,19 @@ fn test_greater_than_expressions() {
     let expected_ast = vec![ast::Statement::ExpressionStatement {
         expression: ast::Expression::InfixExpression {
             operation: ast::InfixOperation::GreaterThan,
-            left: Box::new(ast::Expression::IntegerLiteral { value: 51 }),
+            left: Box::new(ast::Expression::IntegerLiteral {
+                value: 51,
+                span: Span::default(),
+            }),
             right: Box::new(ast::Expression::IdentifierExpression {
                 identifier: String::from("mandarina"),
+                span: Span::default(),
             }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 #[test]
 fn test_less_than_equal_expressions() {
@@ -229,13 +484,19 @@ fn test_less_than_equal_expressions() {
     let expected_ast = vec![ast::Statement::ExpressionStatement {
         expression: ast::Expression::InfixExpression {
             operation: ast::InfixOperation::LessThanEqual,
-            left: Box::new(ast::Expression::IntegerLiteral { value: 51 }),
+            left: Box::new(ast::Expression::IntegerLiteral {
+                value: 51,
+                span: Span::default(),
+            }),
             right: Box::new(ast::Expression::IdentifierExpression {
                 identifier: String::from("mandarina"),
+                span: Span::default(),
             }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 #[test]
 fn test_greater_than_equal_expressions() {
@@ -246,11 +507,17 @@ fn test_greater_than_equal_expressions() {
             operation: ast::InfixOperation::GreaterThanEqual,
             left: Box::new(ast::Expression::IdentifierExpression {
                 identifier: String::from("mandarina"),
+                span: Span::default(),
             }),
-            right: Box::new(ast::Expression::IntegerLiteral { value: 51 }),
+            right: Box::new(ast::Expression::IntegerLiteral {
+                value: 51,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -260,16 +527,28 @@ fn test_expression_precedence_1() {
     let expected_ast = vec![ast::Statement::ExpressionStatement {
         expression: ast::Expression::InfixExpression {
             operation: ast::InfixOperation::Sum,
-            left: Box::new(ast::Expression::IntegerLiteral { value: 5 }),
+            left: Box::new(ast::Expression::IntegerLiteral {
+                value: 5,
+                span: Span::default(),
+            }),
             right: Box::new(ast::Expression::InfixExpression {
                 operation: ast::InfixOperation::Product,
-                left: Box::new(ast::Expression::IntegerLiteral { value: 2 }),
-                right: Box::new(ast::Expression::IntegerLiteral { value: 10 }),
+                left: Box::new(ast::Expression::IntegerLiteral {
+                    value: 2,
+                    span: Span::default(),
+                }),
+                right: Box::new(ast::Expression::IntegerLiteral {
+                    value: 10,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -281,14 +560,26 @@ fn test_expression_precedence_1_b() {
             operation: ast::InfixOperation::Sum,
             left: Box::new(ast::Expression::InfixExpression {
                 operation: ast::InfixOperation::Product,
-                left: Box::new(ast::Expression::IntegerLiteral { value: 5 }),
-                right: Box::new(ast::Expression::IntegerLiteral { value: 2 }),
+                left: Box::new(ast::Expression::IntegerLiteral {
+                    value: 5,
+                    span: Span::default(),
+                }),
+                right: Box::new(ast::Expression::IntegerLiteral {
+                    value: 2,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             }),
-            right: Box::new(ast::Expression::IntegerLiteral { value: 10 }),
+            right: Box::new(ast::Expression::IntegerLiteral {
+                value: 10,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -300,13 +591,22 @@ fn test_expression_precedence_2() {
             operation: ast::InfixOperation::Sum,
             left: Box::new(ast::Expression::PrefixExpression {
                 operation: ast::PrefixOperation::Negative,
-                right: Box::new(ast::Expression::IntegerLiteral { value: 5 }),
+                right: Box::new(ast::Expression::IntegerLiteral {
+                    value: 5,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             }),
-            right: Box::new(ast::Expression::IntegerLiteral { value: 2 }),
+            right: Box::new(ast::Expression::IntegerLiteral {
+                value: 2,
+                span: Span::default(),
+            }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -320,18 +620,24 @@ fn test_expression_precedence_3() {
                 operation: ast::InfixOperation::Sum,
                 left: Box::new(ast::Expression::IdentifierExpression {
                     identifier: String::from("a"),
+                    span: Span::default(),
                 }),
                 right: Box::new(ast::Expression::IdentifierExpression {
                     identifier: String::from("b"),
+                    span: Span::default(),
                 }),
+                span: Span::default(),
             }),
             right: Box::new(ast::Expression::IdentifierExpression {
                 identifier: String::from("c"),
+                span: Span::default(),
             }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -347,36 +653,48 @@ fn test_expression_precedence_4() {
                     operation: ast::InfixOperation::Sum,
                     left: Box::new(ast::Expression::IdentifierExpression {
                         identifier: String::from("a"),
+                        span: Span::default(),
                     }),
                     right: Box::new(ast::Expression::InfixExpression {
                         operation: ast::InfixOperation::Product,
                         left: Box::new(ast::Expression::IdentifierExpression {
                             identifier: String::from("b"),
+                            span: Span::default(),
                         }),
 
                         right: Box::new(ast::Expression::IdentifierExpression {
                             identifier: String::from("c"),
+                            span: Span::default(),
                         }),
+                        span: Span::default(),
                     }),
+                    span: Span::default(),
                 }),
                 right: Box::new(ast::Expression::InfixExpression {
                     operation: ast::InfixOperation::Division,
                     left: Box::new(ast::Expression::IdentifierExpression {
                         identifier: String::from("d"),
+                        span: Span::default(),
                     }),
 
                     right: Box::new(ast::Expression::IdentifierExpression {
                         identifier: String::from("e"),
+                        span: Span::default(),
                     }),
+                    span: Span::default(),
                 }),
+                span: Span::default(),
             }),
             right: Box::new(ast::Expression::IdentifierExpression {
                 identifier: String::from("f"),
+                span: Span::default(),
             }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -388,14 +706,26 @@ fn test_grouped_expression_1() {
             operation: ast::InfixOperation::Product,
             left: Box::new(ast::Expression::InfixExpression {
                 operation: ast::InfixOperation::Sum,
-                left: Box::new(ast::Expression::IntegerLiteral { value: 5 }),
-                right: Box::new(ast::Expression::IntegerLiteral { value: 2 }),
+                left: Box::new(ast::Expression::IntegerLiteral {
+                    value: 5,
+                    span: Span::default(),
+                }),
+                right: Box::new(ast::Expression::IntegerLiteral {
+                    value: 2,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
+            }),
+            right: Box::new(ast::Expression::IntegerLiteral {
+                value: 10,
+                span: Span::default(),
             }),
-            right: Box::new(ast::Expression::IntegerLiteral { value: 10 }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -405,16 +735,28 @@ fn test_grouped_expression_2() {
     let expected_ast = vec![ast::Statement::ExpressionStatement {
         expression: ast::Expression::InfixExpression {
             operation: ast::InfixOperation::Product,
-            left: Box::new(ast::Expression::IntegerLiteral { value: 5 }),
+            left: Box::new(ast::Expression::IntegerLiteral {
+                value: 5,
+                span: Span::default(),
+            }),
             right: Box::new(ast::Expression::InfixExpression {
                 operation: ast::InfixOperation::Sum,
-                left: Box::new(ast::Expression::IntegerLiteral { value: 2 }),
-                right: Box::new(ast::Expression::IntegerLiteral { value: 10 }),
+                left: Box::new(ast::Expression::IntegerLiteral {
+                    value: 2,
+                    span: Span::default(),
+                }),
+                right: Box::new(ast::Expression::IntegerLiteral {
+                    value: 10,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -424,20 +766,36 @@ fn test_grouped_expression_nested() {
     let expected_ast = vec![ast::Statement::ExpressionStatement {
         expression: ast::Expression::InfixExpression {
             operation: ast::InfixOperation::Product,
-            left: Box::new(ast::Expression::IntegerLiteral { value: 1 }),
+            left: Box::new(ast::Expression::IntegerLiteral {
+                value: 1,
+                span: Span::default(),
+            }),
             right: Box::new(ast::Expression::InfixExpression {
                 operation: ast::InfixOperation::Product,
                 left: Box::new(ast::Expression::InfixExpression {
                     operation: ast::InfixOperation::Sum,
-                    left: Box::new(ast::Expression::IntegerLiteral { value: 4 }),
-                    right: Box::new(ast::Expression::IntegerLiteral { value: 5 }),
+                    left: Box::new(ast::Expression::IntegerLiteral {
+                        value: 4,
+                        span: Span::default(),
+                    }),
+                    right: Box::new(ast::Expression::IntegerLiteral {
+                        value: 5,
+                        span: Span::default(),
+                    }),
+                    span: Span::default(),
+                }),
+                right: Box::new(ast::Expression::IntegerLiteral {
+                    value: 8,
+                    span: Span::default(),
                 }),
-                right: Box::new(ast::Expression::IntegerLiteral { value: 8 }),
+                span: Span::default(),
             }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -455,31 +813,48 @@ fn test_if_expression() {
                 operation: ast::InfixOperation::LessThanEqual,
                 left: Box::new(ast::Expression::IdentifierExpression {
                     identifier: String::from("x"),
+                    span: Span::default(),
                 }),
-                right: Box::new(ast::Expression::IntegerLiteral { value: 7 }),
+                right: Box::new(ast::Expression::IntegerLiteral {
+                    value: 7,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             }),
             consequence: vec![
                 ast::Statement::LetStatement {
-                    identifier: String::from("z"),
+                    binding: ast::Binding::Identifier {
+                        name: String::from("z"),
+                    },
                     expression: ast::Expression::InfixExpression {
                         operation: ast::InfixOperation::Product,
                         left: Box::new(ast::Expression::IdentifierExpression {
                             identifier: String::from("x"),
+                            span: Span::default(),
+                        }),
+                        right: Box::new(ast::Expression::IntegerLiteral {
+                            value: 2,
+                            span: Span::default(),
                         }),
-                        right: Box::new(ast::Expression::IntegerLiteral { value: 2 }),
+                        span: Span::default(),
                     },
+                    span: Span::default(),
                 },
-                ast::Statement::ReturnStatement {
+                ast::Statement::BlockValueStatement {
                     expression: ast::Expression::IdentifierExpression {
                         identifier: String::from("z"),
+                        span: Span::default(),
                     },
+                    span: Span::default(),
                 },
             ],
             alternative: None,
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -499,33 +874,72 @@ fn test_if_else_expression() {
                 operation: ast::InfixOperation::LessThanEqual,
                 left: Box::new(ast::Expression::IdentifierExpression {
                     identifier: String::from("x"),
+                    span: Span::default(),
                 }),
-                right: Box::new(ast::Expression::IntegerLiteral { value: 7 }),
+                right: Box::new(ast::Expression::IntegerLiteral {
+                    value: 7,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
             }),
             consequence: vec![
                 ast::Statement::LetStatement {
-                    identifier: String::from("z"),
+                    binding: ast::Binding::Identifier {
+                        name: String::from("z"),
+                    },
                     expression: ast::Expression::InfixExpression {
                         operation: ast::InfixOperation::Product,
                         left: Box::new(ast::Expression::IdentifierExpression {
                             identifier: String::from("x"),
+                            span: Span::default(),
+                        }),
+                        right: Box::new(ast::Expression::IntegerLiteral {
+                            value: 2,
+                            span: Span::default(),
                         }),
-                        right: Box::new(ast::Expression::IntegerLiteral { value: 2 }),
+                        span: Span::default(),
                     },
+                    span: Span::default(),
                 },
-                ast::Statement::ReturnStatement {
+                ast::Statement::BlockValueStatement {
                     expression: ast::Expression::IdentifierExpression {
                         identifier: String::from("z"),
+                        span: Span::default(),
                     },
+                    span: Span::default(),
                 },
             ],
-            alternative: Some(vec![ast::Statement::ReturnStatement {
-                expression: ast::Expression::IntegerLiteral { value: 14 },
+            alternative: Some(vec![ast::Statement::BlockValueStatement {
+                expression: ast::Expression::IntegerLiteral {
+                    value: 14,
+                    span: Span::default(),
+                },
+                span: Span::default(),
             }]),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
+}
+
+#[test]
+fn test_else_if_expression() {
+    assert_same_ast(
+        parse("if (a) { 1 } else if (b) { 2 } else if (c) { 3 } else { 4 };"),
+        parse("if (a) { 1 } else { if (b) { 2 } else { if (c) { 3 } else { 4 } } };"),
+    );
+    assert_same_ast(
+        parse("if (a) { 1 } else if (b) { 2 };"),
+        parse("if (a) { 1 } else { if (b) { 2 } };"),
+    );
+
+    let program = parse("if (a) { 1 } else if (b) { 2 } else { 3 }");
+    let span = program[0].span();
+    assert_eq!((span.start, span.end), (0, 41));
+    assert!(parse_errors("if (a) { 1 } else if { 2 };"));
+    assert!(parse_errors("if (a) { 1 } else while (b) { 2 };"));
 }
 
 #[test]
@@ -538,37 +952,56 @@ fn test_assign_if_expression() {
         ";
 
     let expected_ast = vec![ast::Statement::LetStatement {
-        identifier: String::from("a"),
+        binding: ast::Binding::Identifier {
+            name: String::from("a"),
+        },
         expression: ast::Expression::IfExpression {
             condition: Box::new(ast::Expression::InfixExpression {
                 operation: ast::InfixOperation::LessThanEqual,
                 left: Box::new(ast::Expression::IdentifierExpression {
                     identifier: String::from("x"),
+                    span: Span::default(),
+                }),
+                right: Box::new(ast::Expression::IntegerLiteral {
+                    value: 7,
+                    span: Span::default(),
                 }),
-                right: Box::new(ast::Expression::IntegerLiteral { value: 7 }),
+                span: Span::default(),
             }),
             consequence: vec![
                 ast::Statement::LetStatement {
-                    identifier: String::from("z"),
+                    binding: ast::Binding::Identifier {
+                        name: String::from("z"),
+                    },
                     expression: ast::Expression::InfixExpression {
                         operation: ast::InfixOperation::Product,
                         left: Box::new(ast::Expression::IdentifierExpression {
                             identifier: String::from("x"),
+                            span: Span::default(),
+                        }),
+                        right: Box::new(ast::Expression::IntegerLiteral {
+                            value: 2,
+                            span: Span::default(),
                         }),
-                        right: Box::new(ast::Expression::IntegerLiteral { value: 2 }),
+                        span: Span::default(),
                     },
+                    span: Span::default(),
                 },
-                ast::Statement::ReturnStatement {
+                ast::Statement::BlockValueStatement {
                     expression: ast::Expression::IdentifierExpression {
                         identifier: String::from("z"),
+                        span: Span::default(),
                     },
+                    span: Span::default(),
                 },
             ],
             alternative: None,
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -582,36 +1015,49 @@ fn test_function_expression() {
 
     let expected_ast = vec![ast::Statement::ExpressionStatement {
         expression: ast::Expression::FunctionExpression {
-            arguments: vec![String::from("a"), String::from("b"), String::from("c")],
+            arguments: vec![parameter("a"), parameter("b"), parameter("c")],
+            rest: None,
             body: vec![
                 ast::Statement::LetStatement {
-                    identifier: String::from("z"),
+                    binding: ast::Binding::Identifier {
+                        name: String::from("z"),
+                    },
                     expression: ast::Expression::InfixExpression {
                         operation: ast::InfixOperation::Sum,
                         left: Box::new(ast::Expression::IdentifierExpression {
                             identifier: String::from("a"),
+                            span: Span::default(),
                         }),
                         right: Box::new(ast::Expression::IdentifierExpression {
                             identifier: String::from("b"),
+                            span: Span::default(),
                         }),
+                        span: Span::default(),
                     },
+                    span: Span::default(),
                 },
-                ast::Statement::ReturnStatement {
+                ast::Statement::BlockValueStatement {
                     expression: ast::Expression::InfixExpression {
                         operation: ast::InfixOperation::Product,
                         left: Box::new(ast::Expression::IdentifierExpression {
                             identifier: String::from("z"),
+                            span: Span::default(),
                         }),
                         right: Box::new(ast::Expression::IdentifierExpression {
                             identifier: String::from("c"),
+                            span: Span::default(),
                         }),
+                        span: Span::default(),
                     },
+                    span: Span::default(),
                 },
             ],
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -622,16 +1068,23 @@ fn test_function_expression_without_arguments() {
         }
         ";
 
-    let expected_ast = vec![ast::Statement::ReturnStatement {
+    let expected_ast = vec![ast::Statement::BlockValueStatement {
         expression: ast::Expression::FunctionExpression {
             arguments: vec![],
-            body: vec![ast::Statement::ReturnStatement {
-                expression: ast::Expression::IntegerLiteral { value: 5 },
+            rest: None,
+            body: vec![ast::Statement::BlockValueStatement {
+                expression: ast::Expression::IntegerLiteral {
+                    value: 5,
+                    span: Span::default(),
+                },
+                span: Span::default(),
             }],
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -642,22 +1095,31 @@ fn test_function_expression_with_return() {
         }
         ";
 
-    let expected_ast = vec![ast::Statement::ReturnStatement {
+    let expected_ast = vec![ast::Statement::BlockValueStatement {
         expression: ast::Expression::FunctionExpression {
-            arguments: vec![String::from("a")],
+            arguments: vec![parameter("a")],
+            rest: None,
             body: vec![ast::Statement::ReturnStatement {
                 expression: ast::Expression::InfixExpression {
                     operation: ast::InfixOperation::Product,
                     left: Box::new(ast::Expression::IdentifierExpression {
                         identifier: String::from("a"),
+                        span: Span::default(),
                     }),
-                    right: Box::new(ast::Expression::IntegerLiteral { value: 2 }),
+                    right: Box::new(ast::Expression::IntegerLiteral {
+                        value: 2,
+                        span: Span::default(),
+                    }),
+                    span: Span::default(),
                 },
+                span: Span::default(),
             }],
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -679,24 +1141,37 @@ fn test_call_expression() {
         expression: ast::Expression::CallExpression {
             function: Box::new(ast::Expression::IdentifierExpression {
                 identifier: String::from("myfunction"),
+                span: Span::default(),
             }),
             arguments: vec![
-                ast::Expression::IntegerLiteral { value: 4 },
+                ast::Expression::IntegerLiteral {
+                    value: 4,
+                    span: Span::default(),
+                },
                 ast::Expression::IdentifierExpression {
                     identifier: String::from("c"),
+                    span: Span::default(),
                 },
                 ast::Expression::InfixExpression {
                     operation: ast::InfixOperation::Product,
                     left: Box::new(ast::Expression::IdentifierExpression {
                         identifier: String::from("foo"),
+                        span: Span::default(),
+                    }),
+                    right: Box::new(ast::Expression::IntegerLiteral {
+                        value: 2,
+                        span: Span::default(),
                     }),
-                    right: Box::new(ast::Expression::IntegerLiteral { value: 2 }),
+                    span: Span::default(),
                 },
             ],
+            keyword_arguments: vec![],
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -706,22 +1181,36 @@ fn test_inline_call_expression() {
     let expected_ast = vec![ast::Statement::ExpressionStatement {
         expression: ast::Expression::CallExpression {
             function: Box::new(ast::Expression::FunctionExpression {
-                arguments: vec![String::from("a")],
-                body: vec![ast::Statement::ReturnStatement {
+                arguments: vec![parameter("a")],
+                rest: None,
+                body: vec![ast::Statement::BlockValueStatement {
                     expression: ast::Expression::InfixExpression {
                         operation: ast::InfixOperation::Product,
                         left: Box::new(ast::Expression::IdentifierExpression {
                             identifier: String::from("a"),
+                            span: Span::default(),
                         }),
-                        right: Box::new(ast::Expression::IntegerLiteral { value: 2 }),
+                        right: Box::new(ast::Expression::IntegerLiteral {
+                            value: 2,
+                            span: Span::default(),
+                        }),
+                        span: Span::default(),
                     },
+                    span: Span::default(),
                 }],
+                span: Span::default(),
             }),
-            arguments: vec![ast::Expression::IntegerLiteral { value: 4 }],
+            arguments: vec![ast::Expression::IntegerLiteral {
+                value: 4,
+                span: Span::default(),
+            }],
+            keyword_arguments: vec![],
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -731,59 +1220,92 @@ fn test_call_precedence() {
     let expected_ast = vec![ast::Statement::ExpressionStatement {
         expression: ast::Expression::InfixExpression {
             operation: ast::InfixOperation::Sum,
-            left: Box::new(ast::Expression::IntegerLiteral { value: 4 }),
+            left: Box::new(ast::Expression::IntegerLiteral {
+                value: 4,
+                span: Span::default(),
+            }),
             right: Box::new(ast::Expression::InfixExpression {
                 operation: ast::InfixOperation::Product,
                 left: Box::new(ast::Expression::CallExpression {
                     function: Box::new(ast::Expression::IdentifierExpression {
                         identifier: String::from("add"),
+                        span: Span::default(),
                     }),
                     arguments: vec![
                         ast::Expression::CallExpression {
                             function: Box::new(ast::Expression::IdentifierExpression {
                                 identifier: String::from("add"),
+                                span: Span::default(),
                             }),
                             arguments: vec![
-                                ast::Expression::IntegerLiteral { value: 1 },
-                                ast::Expression::IntegerLiteral { value: 2 },
+                                ast::Expression::IntegerLiteral {
+                                    value: 1,
+                                    span: Span::default(),
+                                },
+                                ast::Expression::IntegerLiteral {
+                                    value: 2,
+                                    span: Span::default(),
+                                },
                             ],
+                            keyword_arguments: vec![],
+                            span: Span::default(),
                         },
                         ast::Expression::InfixExpression {
                             operation: ast::InfixOperation::Product,
                             left: Box::new(ast::Expression::IdentifierExpression {
                                 identifier: String::from("a"),
+                                span: Span::default(),
                             }),
-                            right: Box::new(ast::Expression::IntegerLiteral { value: 2 }),
+                            right: Box::new(ast::Expression::IntegerLiteral {
+                                value: 2,
+                                span: Span::default(),
+                            }),
+                            span: Span::default(),
                         },
                     ],
+                    keyword_arguments: vec![],
+                    span: Span::default(),
                 }),
                 right: Box::new(ast::Expression::IdentifierExpression {
                     identifier: String::from("c"),
+                    span: Span::default(),
                 }),
+                span: Span::default(),
             }),
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
 fn test_array() {
     let program = "[\"hola\", 5,true]";
 
-    let expected_ast = vec![ast::Statement::ReturnStatement {
+    let expected_ast = vec![ast::Statement::BlockValueStatement {
         expression: ast::Expression::Array {
             array: vec![
                 ast::Expression::StringLiteral {
                     value: String::from("hola"),
+                    span: Span::default(),
+                },
+                ast::Expression::IntegerLiteral {
+                    value: 5,
+                    span: Span::default(),
+                },
+                ast::Expression::Boolean {
+                    value: true,
+                    span: Span::default(),
                 },
-                ast::Expression::IntegerLiteral { value: 5 },
-                ast::Expression::Boolean { value: true },
             ],
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
@@ -798,45 +1320,1050 @@ fn test_index() {
             expression: ast::Expression::Index {
                 array: Box::new(ast::Expression::IdentifierExpression {
                     identifier: String::from("a"),
+                    span: Span::default(),
+                }),
+                index: Box::new(ast::Expression::IntegerLiteral {
+                    value: 0,
+                    span: Span::default(),
                 }),
-                index: Box::new(ast::Expression::IntegerLiteral { value: 0 }),
+                span: Span::default(),
             },
+            span: Span::default(),
         },
         ast::Statement::ExpressionStatement {
             expression: ast::Expression::Index {
                 array: Box::new(ast::Expression::IdentifierExpression {
                     identifier: String::from("a"),
+                    span: Span::default(),
                 }),
                 index: Box::new(ast::Expression::StringLiteral {
                     value: String::from("b"),
+                    span: Span::default(),
                 }),
+                span: Span::default(),
             },
+            span: Span::default(),
         },
     ];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
 }
 
 #[test]
 fn test_hashmap() {
     let program = "{\"a\": 2, 3: 5}";
 
-    let expected_ast = vec![ast::Statement::ReturnStatement {
+    let expected_ast = vec![ast::Statement::BlockValueStatement {
         expression: ast::Expression::HashMap {
             pairs: vec![
                 (
                     ast::Expression::StringLiteral {
                         value: String::from("a"),
+                        span: Span::default(),
+                    },
+                    ast::Expression::IntegerLiteral {
+                        value: 2,
+                        span: Span::default(),
                     },
-                    ast::Expression::IntegerLiteral { value: 2 },
                 ),
                 (
-                    ast::Expression::IntegerLiteral { value: 3 },
-                    ast::Expression::IntegerLiteral { value: 5 },
+                    ast::Expression::IntegerLiteral {
+                        value: 3,
+                        span: Span::default(),
+                    },
+                    ast::Expression::IntegerLiteral {
+                        value: 5,
+                        span: Span::default(),
+                    },
                 ),
             ],
+            span: Span::default(),
         },
+        span: Span::default(),
     }];
 
-    assert_eq!(parse(program), expected_ast);
+    assert_same_ast(parse(program), expected_ast);
+}
+
+#[test]
+fn test_spans() {
+    let program = parse("let x = 1;\nfoo(x + 22, y)");
+
+    let let_span = program[0].span();
+    assert_eq!((let_span.start, let_span.end), (0, 9));
+
+    if let ast::Statement::BlockValueStatement { expression, span } = &program[1] {
+        assert_eq!(
+            (span.start, span.end, span.line, span.column),
+            (11, 25, 2, 1)
+        );
+
+        if let ast::Expression::CallExpression { arguments, .. } = expression {
+            let sum_span = arguments[0].span();
+            assert_eq!(
+                (sum_span.start, sum_span.end, sum_span.line, sum_span.column),
+                (15, 21, 2, 5)
+            );
+            return;
+        }
+    }
+
+    panic!("Unexpected AST {:?}", program);
+}
+
+#[test]
+fn test_error_span() {
+    let mut parser = Parser::new("let a = 1;\nlet = 2;".chars());
+    let span = parser.parse_program().unwrap_err().span();
+
+    assert_eq!(
+        (span.start, span.end, span.line, span.column),
+        (15, 16, 2, 5)
+    );
+}
+
+#[test]
+fn test_error_diagnostic() {
+    let program = "let x = 2;\nlet y = foo(x, 3;\n";
+    let mut parser = Parser::new(program.chars());
+    let rendered = parser
+        .parse_program()
+        .unwrap_err()
+        .diagnostic()
+        .render(program);
+
+    assert_eq!(
+        rendered,
+        "error: expected `,` or `)`, found `;`
+ --> 2:17
+  |
+2 | let y = foo(x, 3;
+  |                 ^
+note: to close `(` opened here
+ --> 2:12
+  |
+2 | let y = foo(x, 3;
+  |            ^
+"
+    );
+}
+
+#[test]
+fn test_error_display() {
+    let mut parser = Parser::new("if (a) { 1 ".chars());
+    let error = parser.parse_program().unwrap_err();
+
+    assert_eq!(error.to_string(), "expected `}`, found end of input");
+}
+
+#[test]
+fn test_truncated_assignment() {
+    assert_eq!(
+        parse_error("let x = 1; x ="),
+        ParseError::UnexpectedEnd {
+            span: Span::new(13, 14, 1, 14)
+        }
+    );
+    assert_eq!(
+        parse_error("let x = 1; !"),
+        ParseError::UnexpectedEnd {
+            span: Span::new(11, 12, 1, 12)
+        }
+    );
+}
+
+fn parse_error(s: &str) -> ParseError {
+    let mut parser = Parser::new(s.chars());
+    parser.parse_program().unwrap_err()
+}
+
+#[test]
+fn test_unexpected_character() {
+    assert_eq!(
+        parse_error("let a = 5 # 2;"),
+        ParseError::UnexpectedCharacter {
+            character: '#',
+            span: Span::new(10, 11, 1, 11),
+        }
+    );
+}
+
+#[test]
+fn test_unexpected_character_in_expression() {
+    let errors = [
+        parse_error("@"),
+        parse_error("foo(a, $b)"),
+        parse_error("[1, 2 '"),
+        parse_error("if (a) { 1 # }"),
+    ];
+
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e, ParseError::UnexpectedCharacter { .. })));
+}
+
+#[test]
+fn test_unterminated_string() {
+    let error = parse_error("let a = 1;\nputs(\"hola);");
+
+    assert_eq!(
+        error,
+        ParseError::UnterminatedString {
+            span: Span::new(16, 23, 2, 6)
+        }
+    );
+}
+
+fn parse_recovering(s: &str) -> (ast::Program, Vec<ParseError>) {
+    let mut parser = Parser::new(s.chars());
+    parser.parse_program_recovering()
+}
+
+#[test]
+fn test_recovery_reports_all_errors() {
+    let (program, errors) = parse_recovering(
+        "
+        let a = 1;
+        let = 2;
+        let b = (3;
+        let c = a +;
+        let d = 4;
+        ",
+    );
+
+    assert_eq!(
+        errors.iter().map(|e| e.span().line).collect::<Vec<_>>(),
+        vec![3, 4, 5]
+    );
+    assert_eq!(
+        program
+            .iter()
+            .map(|s| match s {
+                ast::Statement::LetStatement {
+                    binding: ast::Binding::Identifier { name },
+                    ..
+                } => name.clone(),
+                _ => String::new(),
+            })
+            .collect::<Vec<_>>(),
+        vec!["a", "d"]
+    );
+}
+
+#[test]
+fn test_recovery_inside_blocks() {
+    let (program, errors) = parse_recovering(
+        "
+        let f = fn(x) {
+            let = x;
+            if (x) { 1 } else { 2 }
+        };
+        let g = if (a) { 1 + };
+        let h = 3;
+        ",
+    );
+
+    assert_eq!(
+        errors.iter().map(|e| e.span().line).collect::<Vec<_>>(),
+        vec![3, 6]
+    );
+    assert_eq!(program.len(), 1);
+}
+
+#[test]
+fn test_recovery_from_missing_semicolon_inside_blocks() {
+    let (program, errors) = parse_recovering("if (true) { let a = 1 }; 5");
+    assert_eq!(
+        errors,
+        vec![ParseError::MissingSemicolon {
+            span: Span::new(12, 21, 1, 13)
+        }]
+    );
+    assert_eq!(program.len(), 1);
+
+    let (program, errors) = parse_recovering("let f = fn() { let a = 1 }; let g = 2; g");
+    assert_eq!(
+        errors,
+        vec![ParseError::MissingSemicolon {
+            span: Span::new(15, 24, 1, 16)
+        }]
+    );
+    assert_eq!(program.len(), 2);
+}
+
+#[test]
+fn test_recovery_without_errors() {
+    let (program, errors) = parse_recovering("let a = 1; a");
+
+    assert!(errors.is_empty());
+    assert_eq!(program.len(), 2);
+}
+
+#[test]
+fn test_trailing_tokens() {
+    let (program, errors) = parse_recovering("let a = 1; a b; }");
+
+    assert_eq!(program.len(), 3);
+    assert!(matches!(errors[0], ParseError::MissingSemicolon { .. }));
+    assert!(matches!(errors[1], ParseError::UnexpectedToken { .. }));
+    assert_eq!(errors.len(), 2);
+}
+
+#[test]
+fn test_comments() {
+    let program = "
+        // The answer
+        let answer = /* not 41 */ 42; // to everything
+    ";
+
+    let expected_ast = vec![ast::Statement::LetStatement {
+        binding: ast::Binding::Identifier {
+            name: String::from("answer"),
+        },
+        expression: ast::Expression::IntegerLiteral {
+            value: 42,
+            span: Span::default(),
+        },
+        span: Span::default(),
+    }];
+
+    assert_same_ast(parse(program), expected_ast);
+}
+
+#[test]
+fn test_unterminated_comment() {
+    let error = parse_error("let a = 1; /* let b = 2;");
+
+    assert_eq!(
+        error,
+        ParseError::UnterminatedComment {
+            span: Span::new(11, 24, 1, 12)
+        }
+    );
+}
+
+#[test]
+fn test_invalid_escape() {
+    assert_eq!(
+        parse_error("let a = \"tab\\tquote\\'\";"),
+        ParseError::InvalidEscape {
+            sequence: String::from("\\'"),
+            span: Span::new(19, 21, 1, 20),
+        }
+    );
+}
+
+#[test]
+fn test_string_interpolation() {
+    let program = r#"let s = "hello ${name}, you have ${len(xs)} items";"#;
+
+    let expected_ast = vec![ast::Statement::LetStatement {
+        binding: ast::Binding::Identifier {
+            name: String::from("s"),
+        },
+        expression: ast::Expression::InterpolatedString {
+            parts: vec![
+                ast::StringPart::Literal {
+                    value: String::from("hello "),
+                },
+                ast::StringPart::Expression {
+                    expression: ast::Expression::IdentifierExpression {
+                        identifier: String::from("name"),
+                        span: Span::default(),
+                    },
+                },
+                ast::StringPart::Literal {
+                    value: String::from(", you have "),
+                },
+                ast::StringPart::Expression {
+                    expression: ast::Expression::CallExpression {
+                        function: Box::new(ast::Expression::IdentifierExpression {
+                            identifier: String::from("len"),
+                            span: Span::default(),
+                        }),
+                        arguments: vec![ast::Expression::IdentifierExpression {
+                            identifier: String::from("xs"),
+                            span: Span::default(),
+                        }],
+                        keyword_arguments: vec![],
+                        span: Span::default(),
+                    },
+                },
+                ast::StringPart::Literal {
+                    value: String::from(" items"),
+                },
+            ],
+            span: Span::default(),
+        },
+        span: Span::default(),
+    }];
+
+    let program = parse(program);
+    assert_same_ast(program.clone(), expected_ast);
+
+    let span = program[0].span();
+    assert_eq!((span.start, span.end), (0, 50));
+}
+
+#[test]
+fn test_string_interpolation_errors() {
+    assert!(parse_errors(r#""a ${}""#));
+    assert!(parse_errors(r#""a ${b c}""#));
+    assert!(parse_errors(r#""a ${b"#));
+}
+
+#[test]
+fn test_float_literal() {
+    let program = "let ratio = 1.5e2;";
+
+    let expected_ast = vec![ast::Statement::LetStatement {
+        binding: ast::Binding::Identifier {
+            name: String::from("ratio"),
+        },
+        expression: ast::Expression::FloatLiteral {
+            value: 150.0,
+            span: Span::default(),
+        },
+        span: Span::default(),
+    }];
+
+    assert_same_ast(parse(program), expected_ast);
+}
+
+#[test]
+fn test_invalid_float_literal() {
+    assert_eq!(
+        parse_error("1e+;"),
+        ParseError::FailedParsingFloat {
+            string: String::from("1e+"),
+            span: Span::new(0, 3, 1, 1),
+        }
+    );
+}
+
+#[test]
+fn test_radix_integer_literals() {
+    let program = "[0xFF, 0b1010, 0o755, 1_000_000, 0x_dead_BEEF];";
+
+    let expected_ast = vec![ast::Statement::ExpressionStatement {
+        expression: ast::Expression::Array {
+            array: [255, 10, 493, 1_000_000, 0xdead_beef]
+                .iter()
+                .map(|value| ast::Expression::IntegerLiteral {
+                    value: *value,
+                    span: Span::default(),
+                })
+                .collect(),
+            span: Span::default(),
+        },
+        span: Span::default(),
+    }];
+
+    assert_same_ast(parse(program), expected_ast);
+}
+
+#[test]
+fn test_invalid_integer_literals() {
+    let cases = [
+        (
+            "0b102;",
+            IntegerError::InvalidDigit {
+                digit: '2',
+                radix: 2,
+            },
+        ),
+        (
+            "0o8;",
+            IntegerError::InvalidDigit {
+                digit: '8',
+                radix: 8,
+            },
+        ),
+        (
+            "0xFG;",
+            IntegerError::InvalidDigit {
+                digit: 'G',
+                radix: 16,
+            },
+        ),
+        ("0x;", IntegerError::NoDigits),
+        ("9223372036854775808;", IntegerError::Overflow),
+        ("0x1_0000_0000_0000_0000;", IntegerError::Overflow),
+    ];
+
+    for (program, expected_reason) in cases.iter() {
+        match parse_error(program) {
+            ParseError::FailedParsingInteger { reason, .. } => assert_eq!(reason, *expected_reason),
+            e => panic!("Unexpected error {:?}", e),
+        }
+    }
+
+    assert_eq!(
+        parse_error("0b102;").to_string(),
+        "invalid integer literal `0b102`: invalid digit `2` in binary literal"
+    );
+}
+
+#[test]
+fn test_logical_operator_precedence() {
+    assert_same_ast(
+        parse("a || b && c == d || !e;"),
+        parse("(a || (b && (c == d))) || (!e);"),
+    );
+    assert_same_ast(parse("a < 1 && b > 2;"), parse("(a < 1) && (b > 2);"));
+}
+
+#[test]
+fn test_arithmetic_and_bitwise_operator_precedence() {
+    assert_same_ast(parse("a + b % c * d;"), parse("a + ((b % c) * d);"));
+    assert_same_ast(parse("-2 ** 2;"), parse("-(2 ** 2);"));
+    assert_same_ast(parse("2 ** 3 ** 2;"), parse("2 ** (3 ** 2);"));
+    assert_same_ast(parse("2 * 3 ** 2;"), parse("2 * (3 ** 2);"));
+    assert_same_ast(
+        parse("a | b ^ c & d << 1 + e;"),
+        parse("a | (b ^ (c & (d << (1 + e))));"),
+    );
+    assert_same_ast(parse("a & b == c;"), parse("(a & b) == c;"));
+    assert_same_ast(parse("~a & b;"), parse("(~a) & b;"));
+}
+
+#[test]
+fn test_while_statement() {
+    let program = "while (a) { break; continue; } a;";
+
+    let expected_ast = vec![
+        ast::Statement::WhileStatement {
+            condition: ast::Expression::IdentifierExpression {
+                identifier: String::from("a"),
+                span: Span::default(),
+            },
+            body: vec![
+                ast::Statement::BreakStatement {
+                    span: Span::default(),
+                },
+                ast::Statement::ContinueStatement {
+                    span: Span::default(),
+                },
+            ],
+            span: Span::default(),
+        },
+        ast::Statement::ExpressionStatement {
+            expression: ast::Expression::IdentifierExpression {
+                identifier: String::from("a"),
+                span: Span::default(),
+            },
+            span: Span::default(),
+        },
+    ];
+
+    let program = parse(program);
+    assert_same_ast(program.clone(), expected_ast);
+
+    let span = program[0].span();
+    assert_eq!((span.start, span.end), (0, 30));
+}
+
+#[test]
+fn test_loop_control_outside_of_loop() {
+    let error = parse_error("let a = 1;\nbreak;");
+    assert_eq!(
+        error,
+        ParseError::OutsideOfLoop {
+            keyword: Token::Break,
+            span: Span::new(11, 16, 2, 1),
+        }
+    );
+    assert_eq!(error.to_string(), "`break` outside of a loop");
+
+    assert_eq!(
+        parse_error("if (a) { continue; }"),
+        ParseError::OutsideOfLoop {
+            keyword: Token::Continue,
+            span: Span::new(9, 17, 1, 10),
+        }
+    );
+    assert_eq!(
+        parse_error("while (a) { let f = fn() { break; }; }"),
+        ParseError::OutsideOfLoop {
+            keyword: Token::Break,
+            span: Span::new(27, 32, 1, 28),
+        }
+    );
+    assert_eq!(
+        parse_error("while (a) { let f = fn(a = if (b) { break; } else { 1 }) { a }; }"),
+        ParseError::OutsideOfLoop {
+            keyword: Token::Break,
+            span: Span::new(36, 41, 1, 37),
+        }
+    );
+    assert_eq!(
+        parse_error("for (x in xs) { let f = fn(a = if (x) { continue; } else { 1 }) { a }; }"),
+        ParseError::OutsideOfLoop {
+            keyword: Token::Continue,
+            span: Span::new(40, 48, 1, 41),
+        }
+    );
+    assert!(!parse_errors(
+        "while (a) { while (b) { break; } if (c) { continue; } }"
+    ));
+}
+
+#[test]
+fn test_for_statement() {
+    let program = "for (k, v in h) { continue; } for (x in xs) { break; }";
+
+    let identifier = |name: &str| ast::Expression::IdentifierExpression {
+        identifier: String::from(name),
+        span: Span::default(),
+    };
+    let expected_ast = vec![
+        ast::Statement::ForStatement {
+            key: Some(String::from("k")),
+            value: String::from("v"),
+            iterable: identifier("h"),
+            body: vec![ast::Statement::ContinueStatement {
+                span: Span::default(),
+            }],
+            span: Span::default(),
+        },
+        ast::Statement::ForStatement {
+            key: None,
+            value: String::from("x"),
+            iterable: identifier("xs"),
+            body: vec![ast::Statement::BreakStatement {
+                span: Span::default(),
+            }],
+            span: Span::default(),
+        },
+    ];
+
+    assert_same_ast(parse(program), expected_ast);
+}
+
+#[test]
+fn test_invalid_for_statement() {
+    assert_eq!(
+        parse_error("for (x of xs) { }"),
+        ParseError::UnexpectedToken {
+            token: Token::Identifier {
+                name: String::from("of"),
+            },
+            expecting: String::from("`in`"),
+            span: Span::new(7, 9, 1, 8),
+        }
+    );
+    assert!(parse_errors("for (1 in xs) { }"));
+    assert!(parse_errors("for (a, b, c in xs) { }"));
+}
+
+#[test]
+fn test_range_precedence() {
+    assert_same_ast(parse("0..n + 1;"), parse("0..(n + 1);"));
+    assert_same_ast(parse("a || b..=c && d;"), parse("(a || b)..=(c && d);"));
+}
+
+#[test]
+fn test_assign_statement() {
+    let program = "x = 1; total += x * 2;";
+
+    let expected_ast = vec![
+        ast::Statement::AssignStatement {
+            identifier: String::from("x"),
+            operation: None,
+            expression: ast::Expression::IntegerLiteral {
+                value: 1,
+                span: Span::default(),
+            },
+            span: Span::default(),
+        },
+        ast::Statement::AssignStatement {
+            identifier: String::from("total"),
+            operation: Some(ast::InfixOperation::Sum),
+            expression: ast::Expression::InfixExpression {
+                operation: ast::InfixOperation::Product,
+                left: Box::new(ast::Expression::IdentifierExpression {
+                    identifier: String::from("x"),
+                    span: Span::default(),
+                }),
+                right: Box::new(ast::Expression::IntegerLiteral {
+                    value: 2,
+                    span: Span::default(),
+                }),
+                span: Span::default(),
+            },
+            span: Span::default(),
+        },
+    ];
+
+    let program = parse(program);
+    assert_same_ast(program.clone(), expected_ast);
+
+    let span = program[1].span();
+    assert_eq!((span.start, span.end), (7, 21));
+}
+
+#[test]
+fn test_invalid_assignment_target() {
+    let error = parse_error("let a = 1; a + 1 = 2;");
+    assert_eq!(
+        error,
+        ParseError::InvalidAssignmentTarget {
+            span: Span::new(11, 16, 1, 12)
+        }
+    );
+    assert!(parse_errors("x = 1"));
+}
+
+#[test]
+fn test_index_assign_statement() {
+    let program = "m[0][i] *= 2;";
+
+    let identifier = |name: &str| ast::Expression::IdentifierExpression {
+        identifier: String::from(name),
+        span: Span::default(),
+    };
+    let integer = |value| ast::Expression::IntegerLiteral {
+        value,
+        span: Span::default(),
+    };
+    let expected_ast = vec![ast::Statement::IndexAssignStatement {
+        collection: Box::new(ast::Expression::Index {
+            array: Box::new(identifier("m")),
+            index: Box::new(integer(0)),
+            span: Span::default(),
+        }),
+        index: Box::new(identifier("i")),
+        operation: Some(ast::InfixOperation::Product),
+        expression: integer(2),
+        span: Span::default(),
+    }];
+
+    assert_same_ast(parse(program), expected_ast);
+    assert_eq!(
+        parse_error("f()[0] = 1;"),
+        ParseError::InvalidAssignmentTarget {
+            span: Span::new(0, 6, 1, 1)
+        }
+    );
+}
+
+#[test]
+fn test_destructuring_let_statement() {
+    let program = "let [a, [b, c], ...rest] = xs; let {name, age} = person; let [] = [];";
+
+    let name = |name: &str| ast::Binding::Identifier {
+        name: String::from(name),
+    };
+    let identifier = |name: &str| ast::Expression::IdentifierExpression {
+        identifier: String::from(name),
+        span: Span::default(),
+    };
+    let expected_ast = vec![
+        ast::Statement::LetStatement {
+            binding: ast::Binding::Array {
+                elements: vec![
+                    name("a"),
+                    ast::Binding::Array {
+                        elements: vec![name("b"), name("c")],
+                        rest: None,
+                    },
+                ],
+                rest: Some(String::from("rest")),
+            },
+            expression: identifier("xs"),
+            span: Span::default(),
+        },
+        ast::Statement::LetStatement {
+            binding: ast::Binding::HashMap {
+                keys: vec![String::from("name"), String::from("age")],
+            },
+            expression: identifier("person"),
+            span: Span::default(),
+        },
+        ast::Statement::LetStatement {
+            binding: ast::Binding::Array {
+                elements: vec![],
+                rest: None,
+            },
+            expression: ast::Expression::Array {
+                array: vec![],
+                span: Span::default(),
+            },
+            span: Span::default(),
+        },
+    ];
+
+    assert_same_ast(parse(program), expected_ast);
+}
+
+#[test]
+fn test_invalid_destructuring_let_statement() {
+    assert!(parse_errors("let [a, ...rest, b] = xs;"));
+    assert!(parse_errors("let [...] = xs;"));
+    assert!(parse_errors("let [a b] = xs;"));
+    assert!(parse_errors("let {name: n} = person;"));
+    assert!(parse_errors("let {[a]} = person;"));
+    assert!(parse_errors("let 1 = 1;"));
+    assert!(matches!(
+        parse_error("let [a, b = xs;"),
+        ParseError::UnclosedDelimiter { delimiter: '[', .. }
+    ));
+}
+
+#[test]
+fn test_match_expression() {
+    let program = r#"match (x) {
+        0 => "zero",
+        -1.5 => "negative",
+        [first, _, ...rest] if len(rest) > 0 => first,
+        {name, "age": 30} => name,
+        other => other,
+    };"#;
+
+    let identifier = |name: &str| ast::Expression::IdentifierExpression {
+        identifier: String::from(name),
+        span: Span::default(),
+    };
+    let string = |value: &str| ast::Expression::StringLiteral {
+        value: String::from(value),
+        span: Span::default(),
+    };
+    let binding = |name: &str| ast::Pattern::Identifier {
+        name: String::from(name),
+    };
+    let expected_ast = vec![ast::Statement::ExpressionStatement {
+        expression: ast::Expression::MatchExpression {
+            value: Box::new(identifier("x")),
+            arms: vec![
+                ast::MatchArm {
+                    pattern: ast::Pattern::Literal {
+                        value: ast::Expression::IntegerLiteral {
+                            value: 0,
+                            span: Span::default(),
+                        },
+                    },
+                    guard: None,
+                    body: string("zero"),
+                },
+                ast::MatchArm {
+                    pattern: ast::Pattern::Literal {
+                        value: ast::Expression::PrefixExpression {
+                            operation: ast::PrefixOperation::Negative,
+                            right: Box::new(ast::Expression::FloatLiteral {
+                                value: 1.5,
+                                span: Span::default(),
+                            }),
+                            span: Span::default(),
+                        },
+                    },
+                    guard: None,
+                    body: string("negative"),
+                },
+                ast::MatchArm {
+                    pattern: ast::Pattern::Array {
+                        elements: vec![binding("first"), ast::Pattern::Wildcard],
+                        rest: Some(String::from("rest")),
+                    },
+                    guard: Some(ast::Expression::InfixExpression {
+                        operation: ast::InfixOperation::GreaterThan,
+                        left: Box::new(ast::Expression::CallExpression {
+                            function: Box::new(identifier("len")),
+                            arguments: vec![identifier("rest")],
+                            keyword_arguments: vec![],
+                            span: Span::default(),
+                        }),
+                        right: Box::new(ast::Expression::IntegerLiteral {
+                            value: 0,
+                            span: Span::default(),
+                        }),
+                        span: Span::default(),
+                    }),
+                    body: identifier("first"),
+                },
+                ast::MatchArm {
+                    pattern: ast::Pattern::HashMap {
+                        pairs: vec![
+                            (String::from("name"), binding("name")),
+                            (
+                                String::from("age"),
+                                ast::Pattern::Literal {
+                                    value: ast::Expression::IntegerLiteral {
+                                        value: 30,
+                                        span: Span::default(),
+                                    },
+                                },
+                            ),
+                        ],
+                    },
+                    guard: None,
+                    body: identifier("name"),
+                },
+                ast::MatchArm {
+                    pattern: binding("other"),
+                    guard: None,
+                    body: identifier("other"),
+                },
+            ],
+            span: Span::default(),
+        },
+        span: Span::default(),
+    }];
+
+    assert_same_ast(parse(program), expected_ast);
+    assert_same_ast(
+        parse("match (x) {};"),
+        vec![ast::Statement::ExpressionStatement {
+            expression: ast::Expression::MatchExpression {
+                value: Box::new(identifier("x")),
+                arms: vec![],
+                span: Span::default(),
+            },
+            span: Span::default(),
+        }],
+    );
+}
+
+#[test]
+fn test_invalid_match_expression() {
+    assert!(parse_errors("match (x) { 1 => 2 3 => 4 };"));
+    assert!(parse_errors("match (x) { 1 2 };"));
+    assert!(parse_errors("match (x) { a + 1 => 2 };"));
+    assert!(parse_errors("match (x) { -a => 2 };"));
+    assert!(parse_errors("match (x) { \"a ${b}\" => 2 };"));
+    assert!(parse_errors("match (x) { {\"a\"} => 2 };"));
+    assert!(parse_errors("match x { _ => 2 };"));
+    assert!(matches!(
+        parse_error("match (x) { _ => 1"),
+        ParseError::UnclosedDelimiter { delimiter: '{', .. }
+    ));
+}
+
+#[test]
+fn test_function_parameters() {
+    let program = "fn(a, b = a * 2, ...rest) { rest };";
+
+    let identifier = |name: &str| ast::Expression::IdentifierExpression {
+        identifier: String::from(name),
+        span: Span::default(),
+    };
+    let expected_ast = vec![ast::Statement::ExpressionStatement {
+        expression: ast::Expression::FunctionExpression {
+            arguments: vec![
+                parameter("a"),
+                ast::Parameter {
+                    name: String::from("b"),
+                    default: Some(ast::Expression::InfixExpression {
+                        operation: ast::InfixOperation::Product,
+                        left: Box::new(identifier("a")),
+                        right: Box::new(ast::Expression::IntegerLiteral {
+                            value: 2,
+                            span: Span::default(),
+                        }),
+                        span: Span::default(),
+                    }),
+                },
+            ],
+            rest: Some(String::from("rest")),
+            body: vec![ast::Statement::BlockValueStatement {
+                expression: identifier("rest"),
+                span: Span::default(),
+            }],
+            span: Span::default(),
+        },
+        span: Span::default(),
+    }];
+
+    assert_same_ast(parse(program), expected_ast);
+    assert!(parse_errors("fn(...rest, a) { 1 };"));
+    assert!(parse_errors("fn(a = ) { 1 };"));
+    assert_eq!(
+        parse_error("fn(a, 1) { 1 };"),
+        ParseError::NonIdentifierExpression {
+            span: Span::new(6, 7, 1, 7)
+        }
+    );
+    assert_eq!(
+        parse_error("fn(a = 1, b) { 1 };"),
+        ParseError::RequiredAfterDefaultParameter {
+            span: Span::new(10, 11, 1, 11)
+        }
+    );
+    assert_eq!(
+        parse_error("fn(a, a) { 1 };"),
+        ParseError::DuplicateParameter {
+            name: String::from("a"),
+            span: Span::new(6, 7, 1, 7)
+        }
+    );
+    assert_eq!(
+        parse_error("fn(a, ...a) { 1 };"),
+        ParseError::DuplicateParameter {
+            name: String::from("a"),
+            span: Span::new(9, 10, 1, 10)
+        }
+    );
+}
+
+#[test]
+fn test_keyword_arguments() {
+    let program = "f(1, key: x + 1, other: {\"a\": 1});";
+
+    let expected_ast = vec![ast::Statement::ExpressionStatement {
+        expression: ast::Expression::CallExpression {
+            function: Box::new(ast::Expression::IdentifierExpression {
+                identifier: String::from("f"),
+                span: Span::default(),
+            }),
+            arguments: vec![ast::Expression::IntegerLiteral {
+                value: 1,
+                span: Span::default(),
+            }],
+            keyword_arguments: vec![
+                (
+                    String::from("key"),
+                    ast::Expression::InfixExpression {
+                        operation: ast::InfixOperation::Sum,
+                        left: Box::new(ast::Expression::IdentifierExpression {
+                            identifier: String::from("x"),
+                            span: Span::default(),
+                        }),
+                        right: Box::new(ast::Expression::IntegerLiteral {
+                            value: 1,
+                            span: Span::default(),
+                        }),
+                        span: Span::default(),
+                    },
+                ),
+                (
+                    String::from("other"),
+                    ast::Expression::HashMap {
+                        pairs: vec![(
+                            ast::Expression::StringLiteral {
+                                value: String::from("a"),
+                                span: Span::default(),
+                            },
+                            ast::Expression::IntegerLiteral {
+                                value: 1,
+                                span: Span::default(),
+                            },
+                        )],
+                        span: Span::default(),
+                    },
+                ),
+            ],
+            span: Span::default(),
+        },
+        span: Span::default(),
+    }];
+
+    assert_same_ast(parse(program), expected_ast);
+
+    let error = parse_error("f(key: 1, 2);");
+    assert_eq!(
+        error,
+        ParseError::PositionalAfterKeywordArgument {
+            span: Span::new(10, 11, 1, 11)
+        }
+    );
+    assert!(parse_errors("f(1 + 2: 3);"));
 }
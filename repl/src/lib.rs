@@ -6,6 +6,9 @@ use std::io;
 
 pub fn run() {
     let env = new_environment();
+    // Every line entered so far, so errors in functions defined in previous
+    // lines can show the code they point to
+    let mut source = String::new();
 
     loop {
         print!("> ");
@@ -13,18 +16,21 @@ pub fn run() {
         let mut line = String::new();
         io::stdin().read_line(&mut line).unwrap();
 
-        match Parser::new(line.chars()).parse_program() {
-            Err(e) => {
-                println!("There was an error parsing the program");
-                println!("{:?}", e)
+        let line_number = source.matches('\n').count() + 1;
+        let mut parser = Parser::starting_at(line.chars(), source.len(), line_number);
+        let (statements, errors) = parser.parse_program_recovering();
+        source.push_str(&line);
+
+        if !errors.is_empty() {
+            for e in errors {
+                print!("{}", e.diagnostic().render(&source));
             }
-            Ok(statements) => match eval_statements(&env, &statements) {
-                Err(e) => {
-                    println!("There was an error evaluating the program");
-                    println!("{:?}", e)
-                }
-                Ok(o) => println!("{}", o),
-            },
+            continue;
+        }
+
+        match eval_statements(&env, &statements) {
+            Err(e) => print!("{}", e.diagnostic().render(&source)),
+            Ok(o) => println!("{}", o),
         };
     }
 }